@@ -0,0 +1,174 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A container for holding lots of wallets at once, e.g. one per agent in a simulation.
+
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
+
+use crate::{Wallet, CURRENCIES_AS_FARTHINGS};
+
+/// Returned when a batch payment would take more coins out of a wallet than it holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsufficientCoins {
+    /// Index of the first wallet which couldn't pay.
+    pub wallet: usize,
+}
+
+impl Display for InsufficientCoins {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "wallet {} doesn't hold the coins to pay", self.wallet)
+    }
+}
+
+impl core::error::Error for InsufficientCoins {}
+
+/// Many [`Wallet`]s stored as one column of counts per denomination, rather than one struct per
+/// wallet. Batch operations walk a column at a time, which is much kinder to the cache than going
+/// through a `Vec<Wallet>` one wallet at a time.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Wallets {
+    columns: [Vec<usize>; 17],
+}
+
+impl Wallets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Wallets { columns: core::array::from_fn(|_| Vec::with_capacity(capacity)) }
+    }
+
+    /// Number of wallets held.
+    pub fn len(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add a wallet, returning its index.
+    pub fn push(&mut self, wallet: Wallet) -> usize {
+        for (column, count) in self.columns.iter_mut().zip(wallet.to_counts()) {
+            column.push(count);
+        }
+        self.len() - 1
+    }
+
+    /// Get a copy of the wallet at `index`, or [`None`] if it's out of bounds.
+    pub fn get(&self, index: usize) -> Option<Wallet> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(Wallet::from_counts(core::array::from_fn(|d| self.columns[d][index])))
+    }
+
+    /// Replace the wallet at `index`. Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, wallet: Wallet) {
+        for (column, count) in self.columns.iter_mut().zip(wallet.to_counts()) {
+            column[index] = count;
+        }
+    }
+
+    /// Iterate over copies of every wallet.
+    pub fn iter(&self) -> impl Iterator<Item = Wallet> + '_ {
+        (0..self.len()).map(|i| self.get(i).unwrap())
+    }
+
+    /// The farthings value of every wallet, in order.
+    pub fn values(&self) -> Vec<usize> {
+        let mut values = vec![0; self.len()];
+        for (column, &worth) in self.columns.iter().zip(CURRENCIES_AS_FARTHINGS.iter()) {
+            for (value, count) in values.iter_mut().zip(column) {
+                *value += count * worth;
+            }
+        }
+        values
+    }
+
+    /// The farthings value of all wallets together.
+    pub fn total_value(&self) -> usize {
+        self.columns
+            .iter()
+            .zip(CURRENCIES_AS_FARTHINGS.iter())
+            .map(|(column, &worth)| column.iter().sum::<usize>() * worth)
+            .sum()
+    }
+
+    /// Add `receipts[i]` to wallet `i` for every wallet. Panics if the lengths differ.
+    pub fn receive_all(&mut self, receipts: &[Wallet]) {
+        assert_eq!(receipts.len(), self.len(), "need exactly one receipt per wallet");
+        for (wallet, receipt) in receipts.iter().enumerate() {
+            for (column, count) in self.columns.iter_mut().zip(receipt.to_counts()) {
+                column[wallet] += count;
+            }
+        }
+    }
+
+    /// Take `payments[i]` out of wallet `i` for every wallet. Either every payment is made or,
+    /// if any wallet is short, none are. Panics if the lengths differ.
+    pub fn pay_all(&mut self, payments: &[Wallet]) -> Result<(), InsufficientCoins> {
+        assert_eq!(payments.len(), self.len(), "need exactly one payment per wallet");
+        let payments: Vec<[usize; 17]> = payments.iter().map(|p| p.to_counts()).collect();
+        for (wallet, payment) in payments.iter().enumerate() {
+            if (0..17).any(|d| self.columns[d][wallet] < payment[d]) {
+                return Err(InsufficientCoins { wallet });
+            }
+        }
+        for (d, column) in self.columns.iter_mut().enumerate() {
+            for (count, payment) in column.iter_mut().zip(&payments) {
+                *count -= payment[d];
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<Wallet> for Wallets {
+    fn from_iter<T: IntoIterator<Item = Wallet>>(iter: T) -> Self {
+        let mut wallets = Wallets::new();
+        for wallet in iter {
+            wallets.push(wallet);
+        }
+        wallets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_values_and_payments() {
+        let mut wallets: Wallets = [
+            Wallet { florins: 2, pennies: 3, ..Default::default() },
+            Wallet { one_pounds: 1, ..Default::default() },
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(wallets.values(), vec![204, 960]);
+        assert_eq!(wallets.total_value(), 1164);
+
+        let payments = [Wallet { pennies: 1, ..Default::default() }, Wallet { crowns: 1, ..Default::default() }];
+        assert_eq!(wallets.pay_all(&payments), Err(InsufficientCoins { wallet: 1 }));
+        assert_eq!(wallets.values(), vec![204, 960]);
+
+        wallets.receive_all(&[Wallet::default(), Wallet { crowns: 1, ..Default::default() }]);
+        wallets.pay_all(&payments).unwrap();
+        assert_eq!(wallets.get(0), Some(Wallet { florins: 2, pennies: 2, ..Default::default() }));
+        assert_eq!(wallets.get(1), Some(Wallet { one_pounds: 1, ..Default::default() }));
+    }
+}
@@ -0,0 +1,166 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assertion macros for tests which print readable diffs of [`Wallet`]s and [`Price`]s on failure,
+//! instead of dumping every field of both sides.
+
+use alloc::{format, string::{String, ToString}};
+use core::fmt::Write;
+
+use crate::{Price, Wallet, CURRENCIES};
+
+fn value_diff(left: usize, right: usize) -> String {
+    match left.cmp(&right) {
+        core::cmp::Ordering::Less => format!("right is {} farthings more", right - left),
+        core::cmp::Ordering::Greater => format!("left is {} farthings more", left - right),
+        core::cmp::Ordering::Equal => "equal value".to_string(),
+    }
+}
+
+/// Describe how two prices differ. Used by [`assert_price_eq!`](crate::assert_price_eq).
+#[doc(hidden)]
+pub fn price_diff(left: &Price, right: &Price) -> String {
+    format!(
+        "  left: {} ({} farthings)\n right: {} ({} farthings)\n  diff: {}",
+        left,
+        left.to_farthings(),
+        right,
+        right.to_farthings(),
+        value_diff(left.to_farthings(), right.to_farthings()),
+    )
+}
+
+/// Describe how two wallets differ, one line per mismatched denomination. Used by
+/// [`assert_wallet_eq!`](crate::assert_wallet_eq).
+#[doc(hidden)]
+pub fn wallet_diff(left: &Wallet, right: &Wallet) -> String {
+    let mut out = String::new();
+    for currency in CURRENCIES {
+        let (l, r) = (left[currency], right[currency]);
+        if l != r {
+            writeln!(out, "  {}: {} != {}", currency.plural(), l, r).unwrap();
+        }
+    }
+    let (l, r) = (left.to_farthings(), right.to_farthings());
+    write!(
+        out,
+        "  value: {} != {} ({})",
+        Price::from_farthings(l),
+        Price::from_farthings(r),
+        value_diff(l, r),
+    )
+    .unwrap();
+    out
+}
+
+/// Like [`assert_eq!`], but for [`Price`]s. On failure, both prices are shown along with their
+/// farthings values and how far apart they are.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, assert_price_eq, price };
+///
+/// assert_price_eq!(price!(1/5/-), Price::from_pence(300));
+/// ```
+#[macro_export]
+macro_rules! assert_price_eq {
+    ( $left:expr, $right:expr $(,)? ) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed\n{}",
+                        $crate::assertions::price_diff(left, right),
+                    );
+                }
+            }
+        }
+    };
+    ( $left:expr, $right:expr, $($arg:tt)+ ) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed: {}\n{}",
+                        format_args!($($arg)+),
+                        $crate::assertions::price_diff(left, right),
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Like [`assert_eq!`], but for [`Wallet`]s. On failure, only the denominations which differ are
+/// shown, followed by the value of each wallet.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, Wallet, assert_wallet_eq, price };
+///
+/// let expected = Wallet { half_crowns: 1, ..Default::default() };
+/// assert_wallet_eq!(Wallet::from(price!(2/6)), expected);
+/// ```
+#[macro_export]
+macro_rules! assert_wallet_eq {
+    ( $left:expr, $right:expr $(,)? ) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed\n{}",
+                        $crate::assertions::wallet_diff(left, right),
+                    );
+                }
+            }
+        }
+    };
+    ( $left:expr, $right:expr, $($arg:tt)+ ) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed: {}\n{}",
+                        format_args!($($arg)+),
+                        $crate::assertions::wallet_diff(left, right),
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn wallet_diff_lists_mismatches() {
+        let left = Wallet { florins: 1, pennies: 1, ..Default::default() };
+        let right = Wallet { shillings: 2, pennies: 1, ..Default::default() };
+        assert_eq!(
+            wallet_diff(&left, &right),
+            "  shillings: 0 != 2\n  florins: 1 != 0\n  value: £0 2s 1d != £0 2s 1d (equal value)",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "right is 4 farthings more")]
+    fn price_mismatch_panics() {
+        assert_price_eq!(price!(5/2), price!(5/3));
+    }
+}
@@ -0,0 +1,190 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signed amounts of money, for accounts which can go into the red.
+
+use core::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+};
+
+use crate::{Direction, Price, PriceDelta};
+
+/// An amount of money which may be negative, e.g. a customer's account at a shop. Positive means
+/// in credit, negative means in debt.
+///
+/// Displaying a balance puts a minus in front of negative amounts, while the alternate form
+/// (`{:#}`) puts them in brackets like an accountant would.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::balance::Balance;
+///
+/// let mut account = Balance::from(price!(5/-));
+/// account -= price!(7/6);
+/// assert!(account.is_negative());
+/// assert_eq!(account.to_string(), "-£0 2s 6d");
+/// assert_eq!(format!("{:#}", account), "(£0 2s 6d)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Balance {
+    farthings: isize,
+}
+
+impl Balance {
+    pub fn from_farthings(farthings: isize) -> Self {
+        Balance { farthings }
+    }
+
+    pub fn to_farthings(&self) -> isize {
+        self.farthings
+    }
+
+    /// A balance of `price` owed, i.e. `-price`.
+    pub fn debt(price: Price) -> Self {
+        -Self::from(price)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.farthings < 0
+    }
+
+    /// The size of the balance, ignoring whether it's in credit or debt.
+    pub fn magnitude(&self) -> Price {
+        Price::from_farthings(self.farthings.unsigned_abs())
+    }
+
+    /// The balance as a [`Price`], or [`None`] if it's negative.
+    pub fn to_price(&self) -> Option<Price> {
+        usize::try_from(self.farthings).ok().map(Price::from_farthings)
+    }
+}
+
+impl From<Price> for Balance {
+    fn from(value: Price) -> Self {
+        let farthings = isize::try_from(value.to_farthings()).expect("price too large for a balance");
+        Balance { farthings }
+    }
+}
+
+/// Something owed becomes a negative balance, something due a positive one.
+impl From<PriceDelta> for Balance {
+    fn from(value: PriceDelta) -> Self {
+        match value.direction {
+            Direction::Owed => Balance::debt(value.amount),
+            Direction::Due => Balance::from(value.amount),
+        }
+    }
+}
+
+impl Neg for Balance {
+    type Output = Balance;
+
+    fn neg(self) -> Balance {
+        Balance { farthings: -self.farthings }
+    }
+}
+
+impl Add for Balance {
+    type Output = Balance;
+
+    fn add(self, rhs: Balance) -> Balance {
+        Balance { farthings: self.farthings + rhs.farthings }
+    }
+}
+
+impl Sub for Balance {
+    type Output = Balance;
+
+    fn sub(self, rhs: Balance) -> Balance {
+        Balance { farthings: self.farthings - rhs.farthings }
+    }
+}
+
+impl Add<Price> for Balance {
+    type Output = Balance;
+
+    fn add(self, rhs: Price) -> Balance {
+        self + Balance::from(rhs)
+    }
+}
+
+impl Sub<Price> for Balance {
+    type Output = Balance;
+
+    fn sub(self, rhs: Price) -> Balance {
+        self - Balance::from(rhs)
+    }
+}
+
+impl AddAssign for Balance {
+    fn add_assign(&mut self, rhs: Balance) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Balance {
+    fn sub_assign(&mut self, rhs: Balance) {
+        *self = *self - rhs;
+    }
+}
+
+impl AddAssign<Price> for Balance {
+    fn add_assign(&mut self, rhs: Price) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Price> for Balance {
+    fn sub_assign(&mut self, rhs: Price) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Balance {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Balance::default(), |acc, b| acc + b)
+    }
+}
+
+impl Display for Balance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.is_negative(), f.alternate()) {
+            (false, _) => write!(f, "{}", self.magnitude()),
+            (true, false) => write!(f, "-{}", self.magnitude()),
+            (true, true) => write!(f, "({})", self.magnitude()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn going_into_the_red_and_back() {
+        let mut account = Balance::default();
+        account -= price!(1/2/6);
+        account += price!(10/-);
+        assert_eq!(account, Balance::debt(price!(12/6)));
+        assert_eq!(account.to_price(), None);
+        account += Balance::from(price!(1/-/-));
+        assert_eq!(account.to_price(), Some(price!(7/6)));
+        assert_eq!(Balance::from(price!(2/-).delta(price!(2/6))), Balance::debt(price!(-/6)));
+    }
+}
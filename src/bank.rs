@@ -0,0 +1,195 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ordering coins from the bank. Banks only handed out coin in whole bags and packets, so the
+//! coins needed for wage packets or a till float have to be rounded up to what can be ordered.
+
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
+
+use crate::{Currency, Wallet, CURRENCIES};
+
+/// A bag, packet, or single note which can be ordered from the bank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderUnit {
+    pub currency: Currency,
+    /// How many coins (or notes) come in one unit.
+    pub coins: usize,
+}
+
+/// Typical bags and packets: a large bag and a smaller packet for each silver and copper coin, and
+/// gold coins and notes one at a time. Banks differed, so pass your own units to [`order`] if these don't fit.
+pub fn standard_units() -> Vec<OrderUnit> {
+    let unit = |currency, coins| OrderUnit { currency, coins };
+    vec![
+        unit(Currency::Farthing, 96),
+        unit(Currency::Farthing, 48),
+        unit(Currency::Halfpenny, 120),
+        unit(Currency::Halfpenny, 24),
+        unit(Currency::Penny, 60),
+        unit(Currency::Penny, 12),
+        unit(Currency::Threepence, 80),
+        unit(Currency::Threepence, 20),
+        unit(Currency::Groat, 60),
+        unit(Currency::Groat, 15),
+        unit(Currency::Sixpence, 40),
+        unit(Currency::Sixpence, 10),
+        unit(Currency::Shilling, 100),
+        unit(Currency::Shilling, 20),
+        unit(Currency::Florin, 50),
+        unit(Currency::Florin, 10),
+        unit(Currency::HalfCrown, 40),
+        unit(Currency::HalfCrown, 8),
+        unit(Currency::DoubleFlorin, 25),
+        unit(Currency::DoubleFlorin, 5),
+        unit(Currency::Crown, 1),
+        unit(Currency::HalfSovereign, 1),
+        unit(Currency::TenShilling, 1),
+        unit(Currency::Sovereign, 1),
+        unit(Currency::OnePound, 1),
+        unit(Currency::FivePound, 1),
+        unit(Currency::TenPound, 1),
+    ]
+}
+
+/// Returned when coins are needed which none of the order units supply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingUnit(pub Currency);
+
+impl Display for MissingUnit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "no order unit supplies {}", self.0)
+    }
+}
+
+impl core::error::Error for MissingUnit {}
+
+/// What to order from the bank, and what will be left over once the requirements are met.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankOrder {
+    /// How many of each unit to order.
+    pub units: Vec<(OrderUnit, usize)>,
+    /// All the coins and notes which will arrive.
+    pub ordered: Wallet,
+    /// The coins which arrive but aren't needed, e.g. to go into the safe or the till float.
+    pub leftover: Wallet,
+}
+
+/// Choose how many of each unit size to order so that at least `needed` coins arrive, with as
+/// few spare coins as possible and then as few units as possible.
+fn best_order(needed: usize, sizes: &[usize]) -> Vec<usize> {
+    let limit = needed + sizes.iter().max().unwrap();
+    // For every total, the fewest units reaching it exactly and the last unit used to get there.
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; limit + 1];
+    best[0] = Some((0, 0));
+    for total in 1..=limit {
+        for (i, &size) in sizes.iter().enumerate() {
+            if size <= total {
+                if let Some((units, _)) = best[total - size] {
+                    if best[total].is_none_or(|(b, _)| units + 1 < b) {
+                        best[total] = Some((units + 1, i));
+                    }
+                }
+            }
+        }
+    }
+    let mut total = (needed..=limit).find(|&t| best[t].is_some()).unwrap();
+    let mut counts = vec![0; sizes.len()];
+    while total > 0 {
+        let (_, i) = best[total].unwrap();
+        counts[i] += 1;
+        total -= sizes[i];
+    }
+    counts
+}
+
+/// Work out the bank order which covers `required` with the least excess, using the given order
+/// units.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Currency, Wallet };
+/// use coin_changing::bank::{ order, standard_units, OrderUnit };
+///
+/// let required = Wallet { pennies: 70, one_pounds: 3, ..Default::default() };
+/// let order = order(&required, &standard_units()).unwrap();
+/// assert_eq!(order.units, vec![
+///     (OrderUnit { currency: Currency::Penny, coins: 60 }, 1),
+///     (OrderUnit { currency: Currency::Penny, coins: 12 }, 1),
+///     (OrderUnit { currency: Currency::OnePound, coins: 1 }, 3),
+/// ]);
+/// assert_eq!(order.leftover, Wallet { pennies: 2, ..Default::default() });
+/// ```
+pub fn order(required: &Wallet, units: &[OrderUnit]) -> Result<BankOrder, MissingUnit> {
+    let mut ordered = [0; 17];
+    let mut chosen = Vec::new();
+    let needs = CURRENCIES.iter().zip(required.to_counts());
+    for ((&currency, needed), total) in needs.zip(ordered.iter_mut()) {
+        if needed == 0 {
+            continue;
+        }
+        let available: Vec<OrderUnit> = units
+            .iter()
+            .copied()
+            .filter(|u| u.currency == currency && u.coins > 0)
+            .collect();
+        if available.is_empty() {
+            return Err(MissingUnit(currency));
+        }
+        let sizes: Vec<usize> = available.iter().map(|u| u.coins).collect();
+        for (unit, count) in available.into_iter().zip(best_order(needed, &sizes)) {
+            if count > 0 {
+                chosen.push((unit, count));
+                *total += unit.coins * count;
+            }
+        }
+    }
+    let leftover = core::array::from_fn(|d| ordered[d] - required.to_counts()[d]);
+    Ok(BankOrder {
+        units: chosen,
+        ordered: Wallet::from_counts(ordered),
+        leftover: Wallet::from_counts(leftover),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn prefers_least_excess_then_fewest_units() {
+        assert_eq!(best_order(100, &[60, 12]), vec![1, 4]);
+        assert_eq!(best_order(120, &[60, 12]), vec![2, 0]);
+        assert_eq!(best_order(1, &[60, 12]), vec![0, 1]);
+    }
+
+    #[test]
+    fn missing_units() {
+        let required = Wallet { crowns: 1, ..Default::default() };
+        let units = [OrderUnit { currency: Currency::Penny, coins: 60 }];
+        assert_eq!(order(&required, &units), Err(MissingUnit(Currency::Crown)));
+        assert_eq!(MissingUnit(Currency::Crown).to_string(), "no order unit supplies crown");
+    }
+
+    #[test]
+    fn standard_units_cover_everything() {
+        let required = Wallet::from_counts([1; 17]);
+        let order = order(&required, &standard_units()).unwrap();
+        assert!(order.ordered.to_counts().iter().all(|&count| count > 0));
+        let ten_shillings = OrderUnit { currency: Currency::TenShilling, coins: 1 };
+        assert!(order.units.contains(&(ten_shillings, 1)));
+    }
+}
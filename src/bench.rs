@@ -0,0 +1,172 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Standard workloads for timing the crate on your own hardware, e.g. to compare change policies.
+//! Only built with the `bench` feature.
+//!
+//! Allocations are only counted if [`CountingAllocator`] is installed as the global allocator:
+//!
+//! ```
+//! use coin_changing::bench::{ self, CountingAllocator };
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! for report in bench::all(200) {
+//!     println!("{}", report);
+//! }
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{balance::Balance, PayStrategy, Price, Wallet};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, but counting how many allocations are made so that [`BenchReport`]s
+/// can include them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+impl CountingAllocator {
+    /// Whether this is the global allocator, found by allocating and seeing if it's counted.
+    pub fn is_installed() -> bool {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        drop(black_box(Box::new(0u8)));
+        ALLOCATIONS.load(Ordering::Relaxed) != before
+    }
+}
+
+/// How long a workload took.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchReport {
+    pub name: &'static str,
+    /// How many times the workload's basic step was done.
+    pub iterations: usize,
+    pub elapsed: Duration,
+    /// Heap allocations made, or [`None`] if [`CountingAllocator`] isn't installed.
+    pub allocations: Option<usize>,
+}
+
+impl BenchReport {
+    /// The average time for each step.
+    pub fn per_iteration(&self) -> Duration {
+        self.elapsed / self.iterations.max(1) as u32
+    }
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let each = self.per_iteration();
+        write!(f, "{}: {} iterations in {:?} ({:?} each)", self.name, self.iterations, self.elapsed, each)?;
+        match self.allocations {
+            Some(allocations) => write!(f, ", {} allocations", allocations),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Time `work`, which does `iterations` steps.
+fn measure(name: &'static str, iterations: usize, work: impl FnOnce()) -> BenchReport {
+    let counting = CountingAllocator::is_installed();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    work();
+    let elapsed = start.elapsed();
+    let allocations = counting.then(|| ALLOCATIONS.load(Ordering::Relaxed) - before);
+    BenchReport { name, iterations, elapsed, allocations }
+}
+
+/// Prices spread over a few pounds, the same every run so results can be compared.
+fn prices(count: usize) -> impl Iterator<Item = Price> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..count).map(move |_| {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        Price::from_farthings((state % 5_000) as usize)
+    })
+}
+
+/// Turn prices into text and back.
+pub fn price_conversion(iterations: usize) -> BenchReport {
+    measure("price conversion", iterations, || {
+        for price in prices(iterations) {
+            let parsed: Price = black_box(price.to_string()).parse().unwrap();
+            black_box(parsed);
+        }
+    })
+}
+
+/// Add up a long column of credits and debits.
+pub fn bulk_sum(entries: usize) -> BenchReport {
+    let balances: Vec<Balance> =
+        prices(entries).enumerate().map(|(i, p)| if i % 3 == 0 { Balance::debt(p) } else { p.into() }).collect();
+    measure("bulk sum", entries, || {
+        black_box(black_box(&balances).iter().copied().sum::<Balance>());
+    })
+}
+
+/// A day at a shop counter: each customer pays from a full purse, and the till takes the coins
+/// handed over and gives change.
+pub fn trading_day(customers: usize) -> BenchReport {
+    measure("trading day", customers, || {
+        let mut till = Wallet { pennies: 100, sixpence: 40, shillings: 40, florins: 20, ..Default::default() };
+        for price in prices(customers) {
+            let mut purse = Wallet { pennies: 12, shillings: 5, half_crowns: 2, one_pounds: 5, ..Default::default() };
+            let Some(payment) = purse.pay(price, PayStrategy::FewestCoins) else { continue };
+            till += payment.tendered;
+            if let Ok(change) = till.make_change(payment.change_due) {
+                till = till.try_sub(change).unwrap();
+            }
+        }
+        black_box(till);
+    })
+}
+
+/// Every workload, at a similar size.
+pub fn all(size: usize) -> Vec<BenchReport> {
+    vec![price_conversion(size), bulk_sum(size * 100), trading_day(size)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workloads_run() {
+        let reports = all(20);
+        assert_eq!(reports.len(), 3);
+        assert!(reports.iter().all(|r| r.iterations > 0 && r.allocations.is_none()));
+        assert_eq!(prices(5).collect::<Vec<_>>(), prices(5).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,202 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Change as a count of each denomination, rather than the list of farthings values
+//! [`coin_change`](crate::coin_change) gives.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{
+    try_coin_change_as, try_coin_change_up_to, try_coin_change_with, Algorithm, ChangeError, ChangeMaker, ChangePolicy,
+    Currency, Price, Wallet, DEFAULT_MAX_TARGET,
+};
+
+/// How many of each coin or note some change is made of. Denominations with none aren't in
+/// `counts`.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ coin_change, ChangePolicy, Currency, Price, Wallet, price };
+/// use coin_changing::breakdown::ChangeBreakdown;
+///
+/// let change = ChangeBreakdown::for_price(price!(2/9), ChangePolicy::default()).unwrap();
+/// assert_eq!(change.counts.get(&Currency::HalfCrown), Some(&1));
+/// assert_eq!((change.total_coins(), change.value()), (2, price!(2/9)));
+/// assert_eq!(change.to_vec(), vec![Currency::HalfCrown, Currency::Threepence]);
+/// assert_eq!(Wallet::from(change), Wallet { half_crowns: 1, threepence: 1, ..Default::default() });
+///
+/// let from_farthings = ChangeBreakdown::from_farthing_coins(&coin_change(&[4, 48], 56));
+/// assert_eq!(from_farthings.unwrap().total_coins(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ChangeBreakdown {
+    pub counts: BTreeMap<Currency, usize>,
+}
+
+impl ChangeBreakdown {
+    /// Change for `price` using as few coins and notes as possible under `policy`, like
+    /// [`Wallet::change_for`] but without panicking when the denominations can't make it.
+    pub fn for_price(price: Price, policy: impl Into<ChangePolicy>) -> Result<Self, ChangeError> {
+        let policy = policy.into();
+        let target = policy.round(price).to_farthings();
+        let coins = crate::unbounded_coin_change(&policy.change_denominations(), target)?;
+        Ok(Self::from_farthing_coins(&coins).expect("policy denominations are all real ones"))
+    }
+
+    /// Count up a list of coins given as farthings values, e.g. the output of
+    /// [`coin_change`](crate::coin_change). Returns [`None`] if a value isn't a real denomination.
+    /// Values shared by a coin and a note count as the note.
+    pub fn from_farthing_coins(coins: &[usize]) -> Option<Self> {
+        let mut counts = BTreeMap::new();
+        for &coin in coins {
+            *counts.entry(Currency::from_farthings(coin)?).or_insert(0) += 1;
+        }
+        Some(ChangeBreakdown { counts })
+    }
+
+    pub fn total_coins(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn value(&self) -> Price {
+        self.counts.iter().map(|(&c, &count)| Price::from(c) * count).sum()
+    }
+
+    /// Every coin and note, largest first like [`coin_change`](crate::coin_change).
+    pub fn to_vec(&self) -> Vec<Currency> {
+        self.counts.iter().rev().flat_map(|(&c, &count)| core::iter::repeat_n(c, count)).collect()
+    }
+}
+
+/// Check every coin is a real denomination before going to the trouble of making change.
+fn check_denominations(coins: &[usize]) -> Result<(), ChangeError> {
+    match coins.iter().find(|&&c| Currency::from_farthings(c).is_none()) {
+        Some(&coin) => Err(ChangeError::NotADenomination { coin }),
+        None => Ok(()),
+    }
+}
+
+/// Like [`try_coin_change`](crate::try_coin_change), but counting the change up. The coins and
+/// target are in farthings, and every coin has to be a real denomination.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ ChangeError, Currency };
+/// use coin_changing::breakdown::try_change_breakdown;
+///
+/// let change = try_change_breakdown(&[4, 12, 48], 56).unwrap();
+/// assert_eq!(change.to_vec(), vec![Currency::Shilling, Currency::Penny, Currency::Penny]);
+/// assert_eq!(try_change_breakdown(&[4, 5], 9), Err(ChangeError::NotADenomination { coin: 5 }));
+/// ```
+pub fn try_change_breakdown(coins: &[usize], target: usize) -> Result<ChangeBreakdown, ChangeError> {
+    try_change_breakdown_up_to(coins, target, DEFAULT_MAX_TARGET)
+}
+
+/// Like [`try_coin_change_up_to`], but counting the change up as [`try_change_breakdown`] does.
+pub fn try_change_breakdown_up_to(coins: &[usize], target: usize, max_target: usize) -> Result<ChangeBreakdown, ChangeError> {
+    check_denominations(coins)?;
+    Ok(counted(&try_coin_change_up_to(coins, target, max_target)?))
+}
+
+/// Like [`try_coin_change_as`], but counting the change up as [`try_change_breakdown`] does.
+pub fn try_change_breakdown_as<T>(coins: &[T], target: T) -> Result<ChangeBreakdown, ChangeError>
+where
+    T: Copy + TryInto<usize> + TryFrom<usize>,
+{
+    let converted: Option<Vec<usize>> = coins.iter().map(|&c| c.try_into().ok()).collect();
+    check_denominations(&converted.ok_or(ChangeError::Overflow)?)?;
+    let change = try_coin_change_as(coins, target)?;
+    let change: Option<Vec<usize>> = change.into_iter().map(|c| c.try_into().ok()).collect();
+    Ok(counted(&change.ok_or(ChangeError::Overflow)?))
+}
+
+/// Like [`try_coin_change_with`], but counting the change up as [`try_change_breakdown`] does.
+pub fn try_change_breakdown_with(coins: &[usize], target: usize, algorithm: Algorithm) -> Result<ChangeBreakdown, ChangeError> {
+    check_denominations(coins)?;
+    Ok(counted(&try_coin_change_with(coins, target, algorithm)?))
+}
+
+/// Count up change which is known to be made of real denominations.
+fn counted(change: &[usize]) -> ChangeBreakdown {
+    ChangeBreakdown::from_farthing_coins(change).expect("coins were checked to be real denominations")
+}
+
+impl ChangeMaker {
+    /// Like [`ChangeMaker::change`], but counting the change up as [`try_change_breakdown`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ ChangeMaker, Currency };
+    ///
+    /// let maker = ChangeMaker::new(&[4, 96, 120], 240).unwrap();
+    /// let change = maker.change_breakdown(192).unwrap();
+    /// assert_eq!(change.counts.get(&Currency::Florin), Some(&2));
+    /// ```
+    pub fn change_breakdown(&self, target: usize) -> Result<ChangeBreakdown, ChangeError> {
+        check_denominations(self.coins())?;
+        Ok(counted(&self.change(target)?))
+    }
+}
+
+impl From<Wallet> for ChangeBreakdown {
+    fn from(value: Wallet) -> Self {
+        ChangeBreakdown { counts: value.breakdown() }
+    }
+}
+
+impl From<ChangeBreakdown> for Wallet {
+    fn from(value: ChangeBreakdown) -> Self {
+        let mut wallet = Wallet::default();
+        for (currency, count) in value.counts {
+            wallet[currency] += count;
+        }
+        wallet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{price, HalfpennyPolicy};
+
+    #[test]
+    fn matches_change_for() {
+        for price in [price!(0), price!(-/1), price!(4/11), price!(3/17/9)] {
+            let breakdown = ChangeBreakdown::for_price(price, ChangePolicy::default()).unwrap();
+            assert_eq!(Wallet::from(breakdown.clone()), Wallet::change_for(price, ChangePolicy::default()));
+            assert_eq!(ChangeBreakdown::from(Wallet::from(breakdown.clone())), breakdown);
+        }
+        assert_eq!(ChangeBreakdown::from_farthing_coins(&[3]), None);
+        let withdrawn = ChangeBreakdown::for_price(Price::from_farthings(1), HalfpennyPolicy::Withdrawn);
+        assert_eq!(withdrawn.map(|b| b.value()), Ok(price!(-/1)));
+    }
+
+    #[test]
+    fn counterparts_agree() {
+        let coins = [1, 2, 4, 12, 24, 48, 96, 120, 240, 960];
+        for target in [0, 7, 192, 1_000, 5_000] {
+            let expected = ChangeBreakdown::from_farthing_coins(&crate::coin_change(&coins, target));
+            assert_eq!(try_change_breakdown(&coins, target).ok(), expected);
+            assert_eq!(try_change_breakdown_with(&coins, target, Algorithm::Auto).ok(), expected);
+            assert_eq!(ChangeMaker::new(&coins, 5_000).unwrap().change_breakdown(target).ok(), expected);
+        }
+        let coins: [u64; 3] = [4, 48, 960];
+        assert_eq!(try_change_breakdown_as(&coins, 1_012).unwrap().total_coins(), 3);
+        assert_eq!(try_change_breakdown_up_to(&[4], 8, 4), Err(ChangeError::TooLarge { target: 8, max: 4 }));
+        assert_eq!(try_change_breakdown_with(&[3], 9, Algorithm::Greedy), Err(ChangeError::NotADenomination { coin: 3 }));
+    }
+}
@@ -0,0 +1,189 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Swapping coins with the bank until a wallet has a realistic mix of denominations, e.g. to set
+//! up a till at the start of a simulation.
+
+use alloc::vec::Vec;
+
+use crate::{Currency, Price, Wallet, CURRENCIES, CURRENCIES_AS_FARTHINGS};
+
+/// The mix of denominations a wallet should have, as a share of the number of coins and notes.
+/// Shares are relative, so percentages work but don't have to add up to 100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompositionProfile {
+    shares: [u32; 17],
+}
+
+impl CompositionProfile {
+    /// Set the share of coins which should be `currency`.
+    pub fn with(mut self, currency: Currency, share: u32) -> Self {
+        self.shares[currency as usize] = share;
+        self
+    }
+
+    pub fn share(&self, currency: Currency) -> u32 {
+        self.shares[currency as usize]
+    }
+
+    /// A rough guess at the inside of a shop till in the 1950s: mostly pennies and silver, with
+    /// a few notes.
+    pub fn shop_till() -> Self {
+        CompositionProfile::default()
+            .with(Currency::Halfpenny, 15)
+            .with(Currency::Penny, 30)
+            .with(Currency::Threepence, 12)
+            .with(Currency::Sixpence, 12)
+            .with(Currency::Shilling, 10)
+            .with(Currency::Florin, 8)
+            .with(Currency::HalfCrown, 6)
+            .with(Currency::TenShilling, 4)
+            .with(Currency::OnePound, 3)
+    }
+
+    /// How many of each denomination a wallet worth `farthings` would hold under this profile.
+    fn target_counts(&self, farthings: usize) -> [usize; 17] {
+        let shares = self.shares.iter().zip(CURRENCIES_AS_FARTHINGS);
+        let per_share: u128 = shares.map(|(&s, w)| s as u128 * w as u128).sum();
+        if per_share == 0 {
+            return [0; 17];
+        }
+        core::array::from_fn(|i| (self.shares[i] as u128 * farthings as u128 / per_share) as usize)
+    }
+}
+
+/// Coins swapped between a wallet and the bank, of equal value each way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exchange {
+    /// What the wallet paid in.
+    pub gave: Wallet,
+    /// What the wallet got back.
+    pub received: Wallet,
+}
+
+/// Pick coins worth exactly `value` out of `available`, going for the ones in `wanted` first and
+/// never using denomination `skip`.
+fn pick(value: usize, wanted: [usize; 17], available: [usize; 17], skip: usize) -> Option<[usize; 17]> {
+    let mut picked = [0; 17];
+    let mut left = value;
+    for i in (0..CURRENCIES.len()).rev().filter(|&i| i != skip) {
+        picked[i] = wanted[i].min(available[i]).min(left / CURRENCIES_AS_FARTHINGS[i]);
+        left -= picked[i] * CURRENCIES_AS_FARTHINGS[i];
+    }
+    // What's left is worth less than one coin or note, so making it never needs a table anywhere
+    // near DEFAULT_MAX_TARGET, however much the wallet holds.
+    if left > 0 {
+        let mut rest: [usize; 17] = core::array::from_fn(|i| available[i] - picked[i]);
+        rest[skip] = 0;
+        let extra = Wallet::from_counts(rest).make_change(Price::from_farthings(left)).ok()?;
+        for (p, e) in picked.iter_mut().zip(extra.to_counts()) {
+            *p += e;
+        }
+    }
+    Some(picked)
+}
+
+impl Wallet {
+    /// Swap coins with `bank` to bring this wallet closer to `profile`, without changing its
+    /// value. Each swap is a single coin or note one way for coins worth the same the other way,
+    /// and swaps carry on as long as they help and the bank has the coins. Returns the swaps made,
+    /// in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Price, Wallet };
+    /// use coin_changing::composition::CompositionProfile;
+    ///
+    /// let mut till = Wallet { one_pounds: 1, ..Default::default() };
+    /// let mut bank = Wallet { shillings: 100, pennies: 500, ..Default::default() };
+    /// let profile = CompositionProfile::default().with(Currency::Shilling, 1).with(Currency::Penny, 3);
+    /// let exchanges = till.rebalance_to(&profile, &mut bank);
+    /// assert_eq!(till, Wallet { shillings: 16, pennies: 48, ..Default::default() });
+    /// assert_eq!(exchanges.len(), 1);
+    /// assert_eq!(bank.one_pounds, 1);
+    /// ```
+    pub fn rebalance_to(&mut self, profile: &CompositionProfile, bank: &mut Wallet) -> Vec<Exchange> {
+        let target = profile.target_counts(self.to_farthings());
+        let distance = |counts: &[usize; 17]| -> usize {
+            counts.iter().zip(target).map(|(&c, t)| c.abs_diff(t)).sum()
+        };
+        let mut exchanges = Vec::new();
+        loop {
+            let (counts, banked) = (self.to_counts(), bank.to_counts());
+            let surplus: [usize; 17] = core::array::from_fn(|i| counts[i].saturating_sub(target[i]));
+            let deficit: [usize; 17] = core::array::from_fn(|i| target[i].saturating_sub(counts[i]));
+            let mut candidates = Vec::new();
+            for i in 0..CURRENCIES.len() {
+                let mut one = [0; 17];
+                one[i] = 1;
+                let worth = CURRENCIES_AS_FARTHINGS[i];
+                // Pay in one coin we've too many of, or take out one we're short of.
+                if surplus[i] > 0 {
+                    if let Some(received) = pick(worth, deficit, banked, i) {
+                        candidates.push((one, received));
+                    }
+                }
+                if deficit[i] > 0 && banked[i] > 0 {
+                    if let Some(gave) = pick(worth, surplus, counts, i) {
+                        candidates.push((gave, one));
+                    }
+                }
+            }
+            let after = |(gave, received): &([usize; 17], [usize; 17])| {
+                distance(&core::array::from_fn(|i| counts[i] - gave[i] + received[i]))
+            };
+            let best = candidates.into_iter().min_by_key(after).filter(|c| after(c) < distance(&counts));
+            let Some((gave, received)) = best else { break };
+            *self = Wallet::from_counts(core::array::from_fn(|i| counts[i] - gave[i] + received[i]));
+            *bank = Wallet::from_counts(core::array::from_fn(|i| banked[i] + gave[i] - received[i]));
+            exchanges.push(Exchange { gave: Wallet::from_counts(gave), received: Wallet::from_counts(received) });
+        }
+        exchanges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn swaps_keep_value() {
+        let mut till = Wallet { one_pounds: 5, ..Default::default() };
+        let mut bank = Wallet { half_crowns: 100, pennies: 1000, sixpence: 200, ..Default::default() };
+        let value = till.to_farthings() + bank.to_farthings();
+        let exchanges = till.rebalance_to(&CompositionProfile::shop_till(), &mut bank);
+        assert!(!exchanges.is_empty());
+        assert_eq!(till.to_farthings(), Wallet { one_pounds: 5, ..Default::default() }.to_farthings());
+        assert_eq!(till.to_farthings() + bank.to_farthings(), value);
+        assert!(till.pennies > 0 && till.sixpence > 0 && till.half_crowns > 0);
+        assert!(exchanges.iter().all(|e| e.gave.to_farthings() == e.received.to_farthings()));
+
+        let mut empty_bank = Wallet::default();
+        let mut till = Wallet { one_pounds: 1, ..Default::default() };
+        assert_eq!(till.rebalance_to(&CompositionProfile::shop_till(), &mut empty_bank), vec![]);
+    }
+
+    #[test]
+    fn fortunes_rebalance() {
+        // Worth more than DEFAULT_MAX_TARGET, but each swap is only for a single note.
+        let mut safe = Wallet { ten_pounds: 2_000, ..Default::default() };
+        let mut bank = Wallet { one_pounds: 100, ..Default::default() };
+        let profile = CompositionProfile::default().with(Currency::OnePound, 1).with(Currency::TenPound, 1);
+        let exchanges = safe.rebalance_to(&profile, &mut bank);
+        assert_eq!(exchanges.len(), 10);
+        assert_eq!(safe, Wallet { one_pounds: 100, ten_pounds: 1_990, ..Default::default() });
+    }
+}
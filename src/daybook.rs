@@ -0,0 +1,181 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A day book: money in and out jotted down as it happens during the day, and posted to the
+//! [`Ledger`] in one go at closing time, one entry for each category.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Display;
+
+use crate::{
+    balance::Balance,
+    ledger::{Date, Entry, Ledger, Side},
+    Price,
+};
+
+/// One transaction as it was written down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayBookLine {
+    /// What sort of transaction it was, e.g. "bread" or "wages". Lines in the same category and
+    /// on the same side are posted to the ledger as one entry.
+    pub category: String,
+    pub side: Side,
+    pub amount: Price,
+}
+
+/// Returned by [`DayBook::post_to`] when the day book's entries don't add up to the total it was
+/// cast to. Nothing is posted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Miscast {
+    /// The total the day was cast to.
+    pub cast: Balance,
+    /// What the entries actually add up to.
+    pub entered: Balance,
+}
+
+impl Display for Miscast {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the day was cast to {} but the entries come to {}", self.cast, self.entered)
+    }
+}
+
+impl core::error::Error for Miscast {}
+
+/// A day's transactions, in the order they happened.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::balance::Balance;
+/// use coin_changing::daybook::DayBook;
+/// use coin_changing::ledger::{ Date, Ledger };
+///
+/// let mut day = DayBook::new(Date::new(1952, 3, 1));
+/// day.credit("bread", price!(-/7));
+/// day.credit("cakes", price!(1/2));
+/// day.credit("bread", price!(1/2));
+/// day.debit("flour", price!(2/6));
+/// day.cast(Balance::from(price!(-/5)));
+///
+/// let mut books = Ledger::new();
+/// day.post_to(&mut books).unwrap();
+/// let posted: Vec<(&str, Price)> = books.entries().iter().map(|e| (e.description.as_str(), e.amount)).collect();
+/// assert_eq!(posted, [("bread", price!(1/9)), ("cakes", price!(1/2)), ("flour", price!(2/6))]);
+/// assert_eq!(books.balance(), Balance::from(price!(-/5)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayBook {
+    date: Date,
+    lines: Vec<DayBookLine>,
+    cast: Option<Balance>,
+}
+
+impl DayBook {
+    pub fn new(date: Date) -> Self {
+        DayBook { date, lines: Vec::new(), cast: None }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// Every line, in the order it was written down.
+    pub fn lines(&self) -> &[DayBookLine] {
+        &self.lines
+    }
+
+    /// Write down money coming in.
+    pub fn credit(&mut self, category: impl Into<String>, amount: Price) {
+        self.lines.push(DayBookLine { category: category.into(), side: Side::Credit, amount });
+    }
+
+    /// Write down money going out.
+    pub fn debit(&mut self, category: impl Into<String>, amount: Price) {
+        self.lines.push(DayBookLine { category: category.into(), side: Side::Debit, amount });
+    }
+
+    /// Record the net total the day was added up to by hand, for [`DayBook::post_to`] to check.
+    pub fn cast(&mut self, total: Balance) {
+        self.cast = Some(total);
+    }
+
+    /// What the day's lines add up to: positive if more came in than went out.
+    pub fn net(&self) -> Balance {
+        self.lines
+            .iter()
+            .map(|l| match l.side {
+                Side::Credit => Balance::from(l.amount),
+                Side::Debit => Balance::debt(l.amount),
+            })
+            .sum()
+    }
+
+    /// The ledger entries the day comes to: one for each category on each side, in the order the
+    /// categories first came up.
+    pub fn totals(&self) -> Vec<Entry> {
+        let mut totals: Vec<Entry> = Vec::new();
+        for line in &self.lines {
+            match totals.iter_mut().find(|e| e.description == line.category && e.side == line.side) {
+                Some(entry) => entry.amount += line.amount,
+                None => totals.push(Entry {
+                    date: self.date,
+                    description: line.category.clone(),
+                    side: line.side,
+                    amount: line.amount,
+                }),
+            }
+        }
+        totals
+    }
+
+    /// Post the day's [`totals`](DayBook::totals) to `ledger`, after any entries already there for
+    /// the same day. If the day was [`cast`](DayBook::cast), the lines have to add up to it, or
+    /// nothing is posted.
+    pub fn post_to(&self, ledger: &mut Ledger) -> Result<(), Miscast> {
+        let entered = self.net();
+        if let Some(cast) = self.cast.filter(|&cast| cast != entered) {
+            return Err(Miscast { cast, entered });
+        }
+        for entry in self.totals() {
+            ledger.record(entry);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn miscast_days_are_not_posted() {
+        let mut day = DayBook::new(Date::new(1952, 3, 1));
+        day.credit("bread", price!(1/-));
+        day.debit("bread", price!(-/4));
+        day.cast(Balance::from(price!(-/9)));
+        let mut books = Ledger::new();
+        let error = Miscast { cast: Balance::from(price!(-/9)), entered: Balance::from(price!(-/8)) };
+        assert_eq!(day.post_to(&mut books), Err(error));
+        assert!(books.entries().is_empty());
+
+        // Credits and debits in the same category stay apart.
+        day.cast(Balance::from(price!(-/8)));
+        day.post_to(&mut books).unwrap();
+        assert_eq!(books.entries().len(), 2);
+        assert_eq!(books.balance(), Balance::from(price!(-/8)));
+    }
+}
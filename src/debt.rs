@@ -0,0 +1,245 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Paying off a loan bit by bit while simple interest builds up on it.
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use crate::{
+    balance::Balance,
+    ledger::{Date, Ledger},
+    rounding::{RoundingPolicy, RoundingReport},
+    Price,
+};
+
+/// What happened to a debt in an [`Entry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    Payment,
+    Interest,
+}
+
+/// One line of a debt's statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// How many times interest had been charged before this entry.
+    pub period: usize,
+    pub kind: EntryKind,
+    pub amount: Price,
+    /// What was still owed afterwards, as a negative balance.
+    pub owed: Balance,
+}
+
+/// A loan being paid off. Interest is simple, so it's only ever charged on the principal still
+/// outstanding and never on unpaid interest. Payments go towards interest first and then the
+/// principal.
+///
+/// Displaying a debt gives its statement, one entry per line.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::balance::Balance;
+/// use coin_changing::debt::Debt;
+///
+/// // £10 at 5% a period.
+/// let mut debt = Debt::new(price!(10/-/-), 500);
+/// assert_eq!(debt.charge_interest(), price!(10/-));
+/// assert_eq!(debt.pay(price!(3/-/-)), price!(0));
+/// assert_eq!(debt.principal(), price!(7/10/-));
+/// assert_eq!(debt.charge_interest(), price!(7/6));
+/// assert_eq!(debt.owed(), Balance::debt(price!(7/17/6)));
+/// assert_eq!(debt.pay(price!(8/-/-)), price!(2/6));
+/// assert!(debt.is_settled());
+/// assert_eq!(debt.to_string().lines().next(), Some("1: interest £0 10s 0d, owing (£10 10s 0d)"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Debt {
+    /// Interest charged each period, in hundredths of a percent.
+    rate: u32,
+    rounding: RoundingPolicy,
+    principal: Price,
+    interest: Price,
+    period: usize,
+    entries: Vec<Entry>,
+}
+
+impl Debt {
+    /// Start a debt of `principal`, charging `rate` hundredths of a percent of the outstanding
+    /// principal each period, e.g. 500 for 5%.
+    /// Interest is rounded to the nearest farthing, halves up.
+    pub fn new(principal: Price, rate: u32) -> Self {
+        Self::with_rounding(principal, rate, RoundingPolicy::default())
+    }
+
+    /// Like [`Debt::new`], but rounding interest as `rounding` says, e.g. up to the penny.
+    pub fn with_rounding(principal: Price, rate: u32, rounding: RoundingPolicy) -> Self {
+        Debt { rate, rounding, principal, interest: Price::default(), period: 0, entries: Vec::new() }
+    }
+
+    /// The principal still to be paid off.
+    pub fn principal(&self) -> Price {
+        self.principal
+    }
+
+    /// Interest charged but not paid yet.
+    pub fn interest(&self) -> Price {
+        self.interest
+    }
+
+    /// Everything still owed, as a negative balance.
+    pub fn owed(&self) -> Balance {
+        Balance::debt(self.principal + self.interest)
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.principal + self.interest == Price::default()
+    }
+
+    /// Every payment and interest charge so far, oldest first.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Pay off some of the debt, interest first. Returns however much of `amount` wasn't needed.
+    pub fn pay(&mut self, amount: Price) -> Price {
+        let owed = self.principal + self.interest;
+        let paid = amount.min(owed);
+        let to_interest = paid.min(self.interest);
+        self.interest -= to_interest;
+        self.principal -= paid - to_interest;
+        self.record(EntryKind::Payment, paid);
+        amount - paid
+    }
+
+    /// Move on a period, charging interest on the outstanding principal, rounded by the debt's
+    /// rounding policy. Returns the interest charged.
+    pub fn charge_interest(&mut self) -> Price {
+        self.charge_interest_with_report().0
+    }
+
+    /// Like [`Debt::charge_interest`], but also reporting what the rounding did.
+    pub fn charge_interest_with_report(&mut self) -> (Price, RoundingReport) {
+        let exact = self.principal.to_farthings() as u128 * self.rate as u128;
+        let (farthings, report) = self.rounding.round_ratio_with_report(exact, 10_000);
+        let charged = Price::from_farthings(usize::try_from(farthings).expect("interest too large"));
+        self.period += 1;
+        self.interest += charged;
+        self.record(EntryKind::Interest, charged);
+        (charged, report)
+    }
+
+    /// Post every entry so far to `ledger`, with interest as debits and payments as credits, so a
+    /// ledger opened at the amount borrowed (as a debt) follows what's owed. `date` says which
+    /// day each period's entries go on, from period 0, before any interest was charged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::price;
+    /// use coin_changing::balance::Balance;
+    /// use coin_changing::debt::Debt;
+    /// use coin_changing::ledger::{ Date, Ledger };
+    ///
+    /// let mut debt = Debt::new(price!(10/-/-), 500);
+    /// debt.charge_interest();
+    /// debt.pay(price!(3/-/-));
+    /// let mut books = Ledger::with_opening(Balance::debt(price!(10/-/-)));
+    /// debt.post_to(&mut books, |period| Date::new(1900, period as u8 + 1, 1));
+    /// assert_eq!(books.balance(), debt.owed());
+    /// assert_eq!(books.entries()[0].description, "Interest");
+    /// assert_eq!(books.entries()[1].date, Date::new(1900, 2, 1));
+    /// ```
+    pub fn post_to(&self, ledger: &mut Ledger, mut date: impl FnMut(usize) -> Date) {
+        for entry in &self.entries {
+            match entry.kind {
+                EntryKind::Payment => ledger.credit(date(entry.period), "Payment", entry.amount),
+                EntryKind::Interest => ledger.debit(date(entry.period), "Interest", entry.amount),
+            }
+        }
+    }
+
+    fn record(&mut self, kind: EntryKind, amount: Price) {
+        let owed = self.owed();
+        self.entries.push(Entry { period: self.period, kind, amount, owed });
+    }
+}
+
+impl Display for EntryKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EntryKind::Payment => write!(f, "payment"),
+            EntryKind::Interest => write!(f, "interest"),
+        }
+    }
+}
+
+impl Display for Debt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}: {} {}, owing {:#}", entry.period, entry.kind, entry.amount, entry.owed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ledger::Side, price};
+
+    #[test]
+    fn interest_is_simple() {
+        let mut debt = Debt::new(price!(1/-/-), 1_000);
+        debt.charge_interest();
+        debt.charge_interest();
+        // Unpaid interest doesn't earn interest.
+        assert_eq!(debt.interest(), price!(4/-));
+        assert_eq!(debt.pay(price!(5/-)), price!(0));
+        assert_eq!((debt.interest(), debt.principal()), (price!(0), price!(19/-)));
+        // 10% of 19s is 1s 10.8d, which rounds to 1s 10¾d.
+        assert_eq!(debt.charge_interest(), Price::with_farthings(0, 1, 43));
+        assert_eq!(debt.entries().len(), 4);
+        assert_eq!(debt.entries()[2].kind, EntryKind::Payment);
+        assert_eq!(debt.entries()[2].period, 2);
+        assert_eq!(Debt::new(price!(0), 500).owed(), Balance::default());
+    }
+
+    #[test]
+    fn rounding_interest() {
+        use crate::{rounding::RoundingMode, Currency};
+        let mut debt = Debt::with_rounding(price!(19/-), 1_000, RoundingPolicy::new(RoundingMode::Up, Currency::Penny));
+        let (charged, report) = debt.charge_interest_with_report();
+        assert_eq!(charged, price!(1/11));
+        assert_eq!(report.amount, Price::from_farthings(1));
+        assert_eq!(debt.owed(), Balance::debt(price!(20/11)));
+    }
+
+    #[test]
+    fn posting_follows_what_is_owed() {
+        let mut debt = Debt::new(price!(1/-/-), 1_000);
+        debt.pay(price!(5/-));
+        debt.charge_interest();
+        debt.charge_interest();
+        debt.pay(price!(4/-));
+        let mut books = Ledger::with_opening(Balance::debt(price!(1/-/-)));
+        debt.post_to(&mut books, |period| Date::new(1930, 1, period as u8 + 1));
+        let days: Vec<(u8, Side)> = books.entries().iter().map(|e| (e.date.day(), e.side)).collect();
+        assert_eq!(days, [(1, Side::Credit), (2, Side::Debit), (3, Side::Debit), (3, Side::Credit)]);
+        let owed: Vec<Balance> = books.running_balances().map(|(_, balance)| balance).collect();
+        assert_eq!(owed, debt.entries().iter().map(|e| e.owed).collect::<Vec<_>>());
+    }
+}
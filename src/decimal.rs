@@ -0,0 +1,251 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Converting between £sd and the decimal new pence brought in on 15 February 1971. A new penny
+//! is 2.4 old pence, so most amounts don't convert exactly and have to be rounded. The smallest
+//! decimal coin was the half new penny, so everything is rounded to that.
+
+use core::fmt::Display;
+
+use crate::{farthings_to_pence, rounding::RoundingReport, Price, FARTHINGS_PER_PENNY, FARTHINGS_PER_SHILLING};
+
+/// Half new pence in a shilling, which converts exactly (1s is 5p).
+const HALVES_PER_SHILLING: usize = 10;
+
+/// The Decimal Currency Board's table for pence under a shilling, in half new pence. These are
+/// the nearest half new penny, except that 3d went down to 1p and 9d up to 4p.
+const OFFICIAL_TABLE: [usize; 12] = [0, 1, 2, 2, 3, 4, 5, 6, 7, 8, 8, 9];
+
+/// An amount of decimal money, counted in half new pence.
+///
+/// Amounts under a pound are displayed in new pence, like "2½p", and larger ones in pounds, like
+/// "£1.02½".
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::decimal::NewPence;
+///
+/// assert_eq!(NewPence::from_halves(5).to_string(), "2½p");
+/// assert_eq!(NewPence::from_pence(102).to_string(), "£1.02");
+/// assert_eq!(NewPence::from_halves(205).to_string(), "£1.02½");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NewPence {
+    halves: usize,
+}
+
+impl NewPence {
+    pub fn from_halves(halves: usize) -> Self {
+        NewPence { halves }
+    }
+
+    pub fn from_pence(pence: usize) -> Self {
+        NewPence { halves: pence * 2 }
+    }
+
+    pub fn to_halves(&self) -> usize {
+        self.halves
+    }
+
+    /// Convert back to £sd, rounding as `rounding` says. See [`DecimalRounding`] for what each
+    /// choice does in this direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::price;
+    /// use coin_changing::decimal::{ DecimalRounding, NewPence };
+    ///
+    /// let fourpence = NewPence::from_pence(4);
+    /// assert_eq!(fourpence.to_lsd(DecimalRounding::Official), price!(-/10));
+    /// assert_eq!(fourpence.to_lsd(DecimalRounding::Down).to_string(), "£0 0s 9½d");
+    /// assert_eq!(fourpence.to_lsd(DecimalRounding::Up).to_string(), "£0 0s 9¾d");
+    /// ```
+    pub fn to_lsd(&self, rounding: DecimalRounding) -> Price {
+        self.to_lsd_with_report(rounding).0
+    }
+
+    /// Like [`NewPence::to_lsd`], but also reporting what the rounding did.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::Price;
+    /// use coin_changing::decimal::{ DecimalRounding, NewPence };
+    /// use coin_changing::rounding::RoundingDirection;
+    ///
+    /// // 2½p is exactly 6d, and 1p is 2.4d.
+    /// assert!(NewPence::from_halves(5).to_lsd_with_report(DecimalRounding::Official).1.is_exact());
+    /// let (penny, report) = NewPence::from_pence(1).to_lsd_with_report(DecimalRounding::Official);
+    /// assert_eq!((penny, report.direction), (Price::from_pence(2), RoundingDirection::Down));
+    /// ```
+    pub fn to_lsd_with_report(&self, rounding: DecimalRounding) -> (Price, RoundingReport) {
+        // Each half new penny is 4.8 farthings.
+        let halves = self.halves as u128;
+        let farthings = match rounding {
+            DecimalRounding::Official => {
+                let per_shilling = HALVES_PER_SHILLING as u128;
+                let (shillings, rest) = (halves / per_shilling, halves % per_shilling);
+                // The nearest old penny, which can't be a tie.
+                let pence = (rest * 12 + 5) / 10;
+                shillings * FARTHINGS_PER_SHILLING as u128 + pence * FARTHINGS_PER_PENNY as u128
+            }
+            DecimalRounding::Nearest => (halves * 24 + 2) / 5,
+            DecimalRounding::Up => (halves * 24).div_ceil(5),
+            DecimalRounding::Down => halves * 24 / 5,
+        };
+        let rule = match rounding {
+            DecimalRounding::Official => "Decimal Currency Board, to the nearest old penny",
+            DecimalRounding::Nearest => "round to the nearest farthing",
+            DecimalRounding::Up => "round up to the farthing",
+            DecimalRounding::Down => "round down to the farthing",
+        };
+        let report = RoundingReport::from_ratio(rule, halves * 24, farthings * 5, 5);
+        (Price::from_farthings(usize::try_from(farthings).expect("price too large")), report)
+    }
+}
+
+impl Display for NewPence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (pence, half) = (self.halves / 2, if self.halves % 2 == 1 { "½" } else { "" });
+        if pence == 0 && !half.is_empty() {
+            write!(f, "½p")
+        } else if pence < 100 {
+            write!(f, "{}{}p", pence, half)
+        } else {
+            write!(f, "£{}.{:02}{}", pence / 100, pence % 100, half)
+        }
+    }
+}
+
+/// How to round when converting between £sd and decimal money.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DecimalRounding {
+    /// As the Decimal Currency Board said to. Going to decimal, pounds and shillings convert
+    /// exactly and the pence follow the board's table (6d → 2½p), with any fraction of a penny
+    /// first rounded to the nearest penny, halfpennies up. Coming back, each 5p is a shilling and
+    /// the rest is the nearest old penny.
+    #[default]
+    Official,
+    /// The nearest half new penny going to decimal (ties up), or the nearest farthing coming back.
+    Nearest,
+    /// Up to the next half new penny, or farthing coming back.
+    Up,
+    /// Down to the half new penny, or farthing coming back.
+    Down,
+}
+
+impl Price {
+    /// Convert to decimal money, rounding as `rounding` says.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::price;
+    /// use coin_changing::decimal::{ DecimalRounding, NewPence };
+    ///
+    /// assert_eq!(price!(-/6).to_decimal(DecimalRounding::Official), NewPence::from_halves(5));
+    /// assert_eq!(price!(1/2/6).to_decimal(DecimalRounding::Official).to_string(), "£1.12½");
+    /// // The table rounds 3d down, even though 1.25p is halfway.
+    /// assert_eq!(price!(-/3).to_decimal(DecimalRounding::Official).to_string(), "1p");
+    /// assert_eq!(price!(-/3).to_decimal(DecimalRounding::Nearest).to_string(), "1½p");
+    /// ```
+    pub fn to_decimal(&self, rounding: DecimalRounding) -> NewPence {
+        self.to_decimal_with_report(rounding).0
+    }
+
+    /// Like [`Price::to_decimal`], but also reporting what the rounding did. The amount is in
+    /// £sd, to the nearest farthing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::price;
+    /// use coin_changing::decimal::DecimalRounding;
+    /// use coin_changing::rounding::RoundingDirection;
+    ///
+    /// // 3d is 1¼p, and the table takes it down to 1p, i.e. 2.4d.
+    /// let (_, report) = price!(-/3).to_decimal_with_report(DecimalRounding::Official);
+    /// assert_eq!(report.direction, RoundingDirection::Down);
+    /// assert_eq!(report.amount.to_string(), "£0 0s 0½d");
+    /// ```
+    pub fn to_decimal_with_report(&self, rounding: DecimalRounding) -> (NewPence, RoundingReport) {
+        let farthings = self.to_farthings() as u128;
+        // Each farthing is 5/24 of a half new penny.
+        let halves = match rounding {
+            DecimalRounding::Official => {
+                let (shillings, rest) = (self.to_farthings() / FARTHINGS_PER_SHILLING, self.farthings);
+                let (pence, fraction) = farthings_to_pence(rest);
+                let pence = if fraction * 2 >= FARTHINGS_PER_PENNY { pence + 1 } else { pence };
+                // 11½d rounds up to a whole shilling.
+                let halves = OFFICIAL_TABLE.get(pence).copied().unwrap_or(HALVES_PER_SHILLING);
+                shillings as u128 * HALVES_PER_SHILLING as u128 + halves as u128
+            }
+            DecimalRounding::Nearest => (farthings * 5 + 12) / 24,
+            DecimalRounding::Up => (farthings * 5).div_ceil(24),
+            DecimalRounding::Down => farthings * 5 / 24,
+        };
+        let rule = match rounding {
+            DecimalRounding::Official => "Decimal Currency Board table",
+            DecimalRounding::Nearest => "round to the nearest half new penny",
+            DecimalRounding::Up => "round up to the half new penny",
+            DecimalRounding::Down => "round down to the half new penny",
+        };
+        let report = RoundingReport::from_ratio(rule, farthings * 5, halves * 24, 5);
+        (NewPence::from_halves(halves as usize), report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+    use alloc::string::ToString;
+
+    #[test]
+    fn official_table() {
+        let table = ["0p", "½p", "1p", "1p", "1½p", "2p", "2½p", "3p", "3½p", "4p", "4p", "4½p", "5p"];
+        for (pence, decimal) in table.into_iter().enumerate() {
+            let converted = Price::from_pence(pence).to_decimal(DecimalRounding::Official);
+            assert_eq!(converted.to_string(), decimal, "{}d", pence);
+        }
+        assert_eq!(Price::with_farthings(0, 0, 46).to_decimal(DecimalRounding::Official).to_string(), "5p");
+        assert_eq!(Price::with_farthings(0, 0, 25).to_decimal(DecimalRounding::Official).to_string(), "2½p");
+        assert_eq!(price!(10/-/-).to_decimal(DecimalRounding::Official).to_string(), "£10.00");
+    }
+
+    #[test]
+    fn back_to_lsd() {
+        let table = ["-/-", "-/1", "-/2", "-/4", "-/5", "-/6", "-/7", "-/8", "-/10", "-/11", "1/-"];
+        for (halves, lsd) in table.into_iter().enumerate() {
+            let expected: Price = lsd.parse().unwrap();
+            assert_eq!(NewPence::from_halves(halves).to_lsd(DecimalRounding::Official), expected);
+        }
+        // Whole shillings survive the round trip whichever way things are rounded.
+        let roundings = [DecimalRounding::Official, DecimalRounding::Nearest, DecimalRounding::Up, DecimalRounding::Down];
+        for rounding in roundings {
+            assert_eq!(price!(3/17/-).to_decimal(rounding).to_lsd(rounding), price!(3/17/-));
+        }
+        assert_eq!(NewPence::from_halves(1).to_lsd(DecimalRounding::Nearest), Price::from_farthings(5));
+    }
+
+    #[test]
+    fn up_and_down() {
+        let penny = Price::from_pence(1);
+        assert_eq!(penny.to_decimal(DecimalRounding::Up), NewPence::from_halves(1));
+        assert_eq!(penny.to_decimal(DecimalRounding::Down), NewPence::from_halves(0));
+        assert_eq!(price!(-/9).to_decimal(DecimalRounding::Nearest), NewPence::from_halves(8));
+    }
+}
@@ -0,0 +1,145 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Which coins and notes were around in a given year.
+
+use alloc::vec::Vec;
+
+use crate::{Currency, CURRENCIES, CURRENCIES_AS_FARTHINGS};
+
+/// A set of denominations, e.g. the ones in circulation in a particular year. The default set
+/// holds every denomination.
+///
+/// Converting a set into a [`ChangePolicy`](crate::ChangePolicy) lets it be given to anything
+/// which makes or checks change, and its denominations can be passed straight to
+/// [`coin_change`](crate::coin_change).
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ coin_change, Currency, Price, Wallet, price };
+/// use coin_changing::era::CurrencySet;
+///
+/// let coins = CurrencySet::for_year(1935);
+/// assert!(coins.contains(Currency::TenShilling));
+/// assert!(!coins.contains(Currency::Groat));
+/// assert_eq!(Wallet::change_for(price!(10/-), coins), Wallet { ten_shillings: 1, ..Default::default() });
+/// assert_eq!(coin_change(&coins.denominations(), 64), vec![48, 12, 4]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CurrencySet {
+    /// One bit per denomination, indexed in the same order as `CURRENCIES`.
+    bits: u32,
+}
+
+impl Default for CurrencySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl CurrencySet {
+    pub fn all() -> Self {
+        CurrencySet { bits: (1 << CURRENCIES.len()) - 1 }
+    }
+
+    pub fn empty() -> Self {
+        CurrencySet { bits: 0 }
+    }
+
+    /// The denominations in circulation for most of the given year. Dates are when a coin or
+    /// note started being issued or stopped being legal tender, so e.g. the 1914 set has both the
+    /// sovereign and the pound note.
+    pub fn for_year(year: u32) -> Self {
+        CURRENCIES.iter().filter(|c| in_circulation(**c, year)).copied().collect()
+    }
+
+    pub fn contains(&self, currency: Currency) -> bool {
+        self.bits & (1 << currency as u32) != 0
+    }
+
+    pub fn insert(&mut self, currency: Currency) {
+        self.bits |= 1 << currency as u32;
+    }
+
+    pub fn remove(&mut self, currency: Currency) {
+        self.bits &= !(1 << currency as u32);
+    }
+
+    /// Every denomination in the set, smallest first.
+    pub fn iter(&self) -> impl Iterator<Item = Currency> + '_ {
+        CURRENCIES.iter().copied().filter(|&c| self.contains(c))
+    }
+
+    /// The farthings values of the denominations in the set, smallest first. Coins and notes of
+    /// the same value only appear once.
+    pub fn denominations(&self) -> Vec<usize> {
+        let mut denominations: Vec<usize> = CURRENCIES
+            .iter()
+            .zip(CURRENCIES_AS_FARTHINGS)
+            .filter(|(&c, _)| self.contains(c))
+            .map(|(_, worth)| worth)
+            .collect();
+        denominations.dedup();
+        denominations
+    }
+}
+
+impl FromIterator<Currency> for CurrencySet {
+    fn from_iter<T: IntoIterator<Item = Currency>>(iter: T) -> Self {
+        let mut set = CurrencySet::empty();
+        for currency in iter {
+            set.insert(currency);
+        }
+        set
+    }
+}
+
+/// Whether a denomination was in circulation in the given year.
+pub(crate) fn in_circulation(currency: Currency, year: u32) -> bool {
+    match currency {
+        Currency::Farthing => year <= 1960,
+        Currency::Halfpenny => year <= 1969,
+        Currency::Groat => year <= 1887,
+        Currency::Florin => year >= 1849,
+        Currency::HalfCrown => year <= 1969,
+        Currency::DoubleFlorin => (1887..=1890).contains(&year),
+        Currency::HalfSovereign | Currency::Sovereign => year <= 1914,
+        Currency::TenShilling | Currency::OnePound => year >= 1914,
+        // The white tenner was withdrawn in 1943, and nothing replaced it until 1964.
+        Currency::TenPound => !(1944..=1963).contains(&year),
+        Currency::Penny
+        | Currency::Threepence
+        | Currency::Sixpence
+        | Currency::Shilling
+        | Currency::Crown
+        | Currency::FivePound => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_by_year() {
+        assert!(CurrencySet::for_year(1850).contains(Currency::Groat));
+        assert!(!CurrencySet::for_year(1850).contains(Currency::OnePound));
+        assert!(CurrencySet::for_year(1850).contains(Currency::Sovereign));
+        assert!(!CurrencySet::for_year(1950).contains(Currency::TenPound));
+        assert!(!CurrencySet::for_year(1965).contains(Currency::Farthing));
+        assert_eq!(CurrencySet::for_year(1914).denominations().iter().filter(|&&d| d == 960).count(), 1);
+        assert_eq!(CurrencySet::all().iter().count(), CURRENCIES.len());
+    }
+}
@@ -0,0 +1,121 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Buses and turnstiles which only take the exact fare, in certain coins.
+
+use core::fmt::Display;
+
+use crate::{era::CurrencySet, Price, Wallet};
+
+/// Why a wallet couldn't pay an [`ExactFare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FareError {
+    /// The whole wallet isn't worth the fare.
+    Insufficient { short: Price },
+    /// The wallet's worth enough, but not in coins the machine takes.
+    WrongDenominations { short: Price },
+    /// The coins the machine takes are worth enough, but can't make the fare exactly.
+    NoExactSum,
+}
+
+impl Display for FareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FareError::Insufficient { short } => write!(f, "not enough money, short by {}", short),
+            FareError::WrongDenominations { short } => {
+                write!(f, "not enough in accepted coins, short by {}", short)
+            }
+            FareError::NoExactSum => write!(f, "the accepted coins can't make the exact fare"),
+        }
+    }
+}
+
+impl core::error::Error for FareError {}
+
+/// A fare which has to be paid exactly, using only the accepted coins.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Currency, Price, Wallet, price };
+/// use coin_changing::era::CurrencySet;
+/// use coin_changing::fare::{ ExactFare, FareError };
+///
+/// let pennies_only = CurrencySet::from_iter([Currency::Penny, Currency::Halfpenny]);
+/// let fare = ExactFare::new(price!(-/3), pennies_only);
+/// let mut pocket = Wallet { pennies: 4, sixpence: 1, ..Default::default() };
+/// assert_eq!(fare.take_from(&mut pocket), Ok(Wallet { pennies: 3, ..Default::default() }));
+/// assert_eq!(pocket, Wallet { pennies: 1, sixpence: 1, ..Default::default() });
+/// assert_eq!(fare.take_from(&mut pocket), Err(FareError::WrongDenominations { short: price!(-/2) }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExactFare {
+    pub price: Price,
+    pub accepted: CurrencySet,
+}
+
+impl ExactFare {
+    pub fn new(price: Price, accepted: CurrencySet) -> Self {
+        ExactFare { price, accepted }
+    }
+
+    /// Whether the wallet can pay the fare.
+    pub fn can_satisfy(&self, wallet: &Wallet) -> bool {
+        self.payment(wallet).is_ok()
+    }
+
+    /// The coins which would pay the fare, without taking them.
+    pub fn payment(&self, wallet: &Wallet) -> Result<Wallet, FareError> {
+        let fare = self.price.to_farthings();
+        let short = |worth: usize| Price::from_farthings(fare - worth);
+        if wallet.to_farthings() < fare {
+            return Err(FareError::Insufficient { short: short(wallet.to_farthings()) });
+        }
+        let mut usable = Wallet::default();
+        for currency in self.accepted.iter() {
+            usable[currency] = wallet[currency];
+        }
+        if usable.to_farthings() < fare {
+            return Err(FareError::WrongDenominations { short: short(usable.to_farthings()) });
+        }
+        usable.exact_payment(self.price).ok_or(FareError::NoExactSum)
+    }
+
+    /// Take the fare out of the wallet, returning the coins taken. The wallet's left alone if it
+    /// can't pay.
+    pub fn take_from(&self, wallet: &mut Wallet) -> Result<Wallet, FareError> {
+        let paid = self.payment(wallet)?;
+        *wallet = wallet.try_sub(paid).expect("payment is taken from the wallet's own coins");
+        Ok(paid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{price, Currency};
+
+    #[test]
+    fn reasons_for_refusing() {
+        let fare = ExactFare::new(price!(-/6), CurrencySet::from_iter([Currency::Threepence]));
+        let poor = Wallet { pennies: 5, ..Default::default() };
+        assert_eq!(fare.payment(&poor), Err(FareError::Insufficient { short: price!(-/1) }));
+        let odd = Wallet { threepence: 3, ..Default::default() };
+        assert_eq!(fare.payment(&odd), Ok(Wallet { threepence: 2, ..Default::default() }));
+        let fare = ExactFare::new(price!(-/4), CurrencySet::from_iter([Currency::Threepence]));
+        assert_eq!(fare.payment(&odd), Err(FareError::NoExactSum));
+        assert!(!fare.can_satisfy(&odd));
+        assert!(ExactFare::new(price!(0), CurrencySet::empty()).can_satisfy(&Wallet::default()));
+    }
+}
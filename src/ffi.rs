@@ -0,0 +1,234 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A C interface, for calling the crate from C or anything which can call C (C#, Swift, ...).
+//! Only built with the `ffi` feature. The `coin_changing_ffi` package in the `ffi` directory builds
+//! it as a shared library, with `cargo build --release -p coin_changing_ffi`.
+//!
+//! Prices are handed out as opaque pointers, which must be given back to [`cc_price_free`]. Nothing
+//! here panics across into C: anything which would have returns null (or zero) instead. The
+//! matching C declarations are:
+//!
+//! ```c
+//! typedef struct CcPrice CcPrice;
+//!
+//! CcPrice *cc_price_new(size_t pounds, size_t shillings, size_t halfpence);
+//! CcPrice *cc_price_from_farthings(size_t farthings);
+//! CcPrice *cc_price_parse(const char *s);
+//! void cc_price_free(CcPrice *price);
+//! size_t cc_price_to_farthings(const CcPrice *price);
+//! CcPrice *cc_price_add(const CcPrice *a, const CcPrice *b);
+//! size_t cc_price_format(const CcPrice *price, char *buffer, size_t length);
+//! size_t cc_denomination_count(void);
+//! size_t cc_denomination_farthings(size_t index);
+//! size_t cc_make_change(const CcPrice *price, size_t *counts, size_t length);
+//! ```
+
+use std::{
+    boxed::Box,
+    ffi::{c_char, CStr},
+    panic::{catch_unwind, AssertUnwindSafe},
+    string::ToString,
+};
+
+use crate::{Price, Wallet, CURRENCIES_AS_FARTHINGS};
+
+fn into_raw(price: Price) -> *mut Price {
+    Box::into_raw(Box::new(price))
+}
+
+/// Hand out a price, or null if there isn't one.
+fn into_raw_or_null(price: Option<Price>) -> *mut Price {
+    price.map_or(core::ptr::null_mut(), into_raw)
+}
+
+/// Run `f`, giving `fallback` if it panics, since unwinding into C would abort.
+fn guard<T>(fallback: T, f: impl FnOnce() -> T) -> T {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(fallback)
+}
+
+/// A new price, carrying over amounts too big for their unit like [`Price::new`]. Returns null if
+/// the price is too big to hold.
+#[no_mangle]
+pub extern "C" fn cc_price_new(pounds: usize, shillings: usize, halfpence: usize) -> *mut Price {
+    let farthings = halfpence.checked_mul(2);
+    into_raw_or_null(farthings.and_then(|farthings| Price::try_with_farthings(pounds, shillings, farthings)))
+}
+
+#[no_mangle]
+pub extern "C" fn cc_price_from_farthings(farthings: usize) -> *mut Price {
+    into_raw(Price::from_farthings(farthings))
+}
+
+/// Parse a price in slash or £sd notation (UTF-8), as [`str::parse`] does. Returns null if it
+/// isn't a valid price.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn cc_price_parse(s: *const c_char) -> *mut Price {
+    if s.is_null() {
+        return core::ptr::null_mut();
+    }
+    // SAFETY: the caller promises a NUL-terminated string.
+    let s = unsafe { CStr::from_ptr(s) };
+    into_raw_or_null(guard(None, || s.to_str().ok().and_then(|s| s.parse().ok())))
+}
+
+/// Free a price. Passing null does nothing.
+///
+/// # Safety
+///
+/// `price` must be null or have come from this library, and not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn cc_price_free(price: *mut Price) {
+    if !price.is_null() {
+        // SAFETY: the caller promises the price came from `into_raw` and is still live.
+        drop(unsafe { Box::from_raw(price) });
+    }
+}
+
+/// # Safety
+///
+/// `price` must be a live price from this library.
+#[no_mangle]
+pub unsafe extern "C" fn cc_price_to_farthings(price: *const Price) -> usize {
+    // SAFETY: the caller promises a live price.
+    unsafe { &*price }.to_farthings()
+}
+
+/// A new price which is the sum of two others, or null if it's too big to hold.
+///
+/// # Safety
+///
+/// `a` and `b` must be live prices from this library.
+#[no_mangle]
+pub unsafe extern "C" fn cc_price_add(a: *const Price, b: *const Price) -> *mut Price {
+    // SAFETY: the caller promises live prices.
+    let (a, b) = unsafe { (*a, *b) };
+    into_raw_or_null(a.to_farthings().checked_add(b.to_farthings()).map(Price::from_farthings))
+}
+
+/// Write the price in £sd notation as a NUL-terminated UTF-8 string, like `snprintf`: at most
+/// `length` bytes are written, including the NUL, cutting the text short (between characters) if
+/// needed. Returns the length of the whole text (without the NUL), so a return value of `length`
+/// or more means the buffer was too small.
+///
+/// # Safety
+///
+/// `price` must be a live price from this library, and `buffer` must point to at least `length`
+/// writable bytes (or be null if `length` is zero).
+#[no_mangle]
+pub unsafe extern "C" fn cc_price_format(price: *const Price, buffer: *mut c_char, length: usize) -> usize {
+    // SAFETY: the caller promises a live price.
+    let text = unsafe { &*price }.to_string();
+    if length > 0 {
+        let mut copied = text.len().min(length - 1);
+        while !text.is_char_boundary(copied) {
+            copied -= 1;
+        }
+        // SAFETY: the caller promises `length` writable bytes, and at most that many are written.
+        unsafe {
+            core::ptr::copy_nonoverlapping(text.as_ptr(), buffer.cast::<u8>(), copied);
+            *buffer.add(copied) = 0;
+        }
+    }
+    text.len()
+}
+
+/// How many denominations there are, i.e. how long the array given to [`cc_make_change`] needs
+/// to be.
+#[no_mangle]
+pub extern "C" fn cc_denomination_count() -> usize {
+    CURRENCIES_AS_FARTHINGS.len()
+}
+
+/// What the denomination at `index` is worth in farthings, smallest first, or zero if there's no
+/// such denomination.
+#[no_mangle]
+pub extern "C" fn cc_denomination_farthings(index: usize) -> usize {
+    CURRENCIES_AS_FARTHINGS.get(index).copied().unwrap_or(0)
+}
+
+/// Make change for a price with the default policy, as [`Wallet::from`] does, writing how many
+/// of each denomination to use into `counts` (smallest denomination first). Returns the number of
+/// denominations; if `length` is less than that, nothing is written. Returns zero, writing
+/// nothing, if change couldn't be made.
+///
+/// # Safety
+///
+/// `price` must be a live price from this library, and `counts` must point to at least `length`
+/// writable `size_t`s.
+#[no_mangle]
+pub unsafe extern "C" fn cc_make_change(price: *const Price, counts: *mut usize, length: usize) -> usize {
+    let needed = CURRENCIES_AS_FARTHINGS.len();
+    if length >= needed {
+        // SAFETY: the caller promises a live price.
+        let price = unsafe { *price };
+        let Some(change) = guard(None, || Some(Wallet::from(price).to_counts())) else {
+            return 0;
+        };
+        // SAFETY: the caller promises `length` writable counts, and only `needed` are written.
+        unsafe { core::ptr::copy_nonoverlapping(change.as_ptr(), counts, needed) };
+    }
+    needed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn round_trip_through_c() {
+        unsafe {
+            let a = cc_price_new(1, 2, 13);
+            let b = cc_price_parse(c"7/6".as_ptr());
+            let sum = cc_price_add(a, b);
+            assert_eq!(cc_price_to_farthings(sum), price!(1/10/-).to_farthings() + 2);
+            let mut buffer = [0 as c_char; 11];
+            let length = cc_price_format(sum, buffer.as_mut_ptr(), buffer.len());
+            assert_eq!(length, "£1 10s 0½d".len());
+            assert_eq!(CStr::from_ptr(buffer.as_ptr()).to_bytes(), "£1 10s 0".as_bytes());
+            let mut counts = [0; 17];
+            assert_eq!(cc_make_change(b, counts.as_mut_ptr(), 3), 17);
+            assert_eq!(counts, [0; 17]);
+            cc_make_change(b, counts.as_mut_ptr(), counts.len());
+            assert_eq!(Wallet::from_counts(counts), Wallet::from(price!(7/6)));
+            assert!(cc_price_parse(c"nonsense".as_ptr()).is_null());
+            for price in [a, b, sum, core::ptr::null_mut()] {
+                cc_price_free(price);
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_panics_into_c() {
+        unsafe {
+            assert!(cc_price_new(usize::MAX, 0, 0).is_null());
+            assert!(cc_price_new(0, 0, usize::MAX).is_null());
+            let big = cc_price_from_farthings(usize::MAX);
+            assert!(cc_price_add(big, big).is_null());
+            assert!(cc_price_parse(c"20000000000000000/-/-".as_ptr()).is_null());
+            let fortune = cc_price_new(20_000, 0, 0);
+            let mut counts = [0; 17];
+            assert_eq!(cc_make_change(fortune, counts.as_mut_ptr(), counts.len()), 17);
+            assert_eq!(counts[16], 2_000);
+            for price in [big, fortune] {
+                cc_price_free(price);
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Everything known about each coin and note in one place, for anything wanting to describe
+//! them rather than just count them.
+
+use alloc::vec::Vec;
+
+use crate::{era, metal::CoinSpec, Currency};
+
+/// The years this crate covers, from the Great Recoinage to decimalisation.
+const FIRST_YEAR: u32 = 1816;
+const LAST_YEAR: u32 = 1971;
+
+/// Years in which the make-up of some coin changed: bronze replacing copper, debased silver,
+/// the brass threepence, and cupronickel.
+const CHANGES: [u32; 4] = [1860, 1920, 1942, 1947];
+
+/// What's known about a denomination, as returned by [`Currency::info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencyInfo {
+    pub currency: Currency,
+    /// The denomination as the Mint or the Bank would have written it, e.g. "two shillings" for
+    /// the florin.
+    pub formal_name: &'static str,
+    /// The first year it was issued, or [`None`] if it was already around in 1816.
+    pub first_year: Option<u32>,
+    /// The last year it was legal tender, or [`None`] if it lasted until decimalisation.
+    pub last_year: Option<u32>,
+    /// The diameter of the last ones struck, in micrometres, or [`None`] for notes.
+    pub diameter: Option<u32>,
+    /// The mass of the last ones struck, in milligrams, or [`None`] for notes.
+    pub mass: Option<u32>,
+    /// What it was called on the street, most common first.
+    pub slang: &'static [&'static str],
+}
+
+impl CurrencyInfo {
+    /// Whether it was in circulation for most of `year`. Unlike the first and last years, this
+    /// knows about gaps, like the ten pound note not being issued from 1944 to 1963.
+    pub fn in_circulation(&self, year: u32) -> bool {
+        era::in_circulation(self.currency, year)
+    }
+
+    /// Every make-up the denomination had, each with the year it started (or [`None`] for the
+    /// first), oldest first.
+    pub fn compositions(&self) -> Vec<(Option<u32>, CoinSpec)> {
+        let mut compositions = alloc::vec![(None, self.currency.spec(FIRST_YEAR))];
+        for year in CHANGES {
+            let spec = self.currency.spec(year);
+            if compositions.last().is_some_and(|&(_, last)| last != spec) {
+                compositions.push((Some(year), spec));
+            }
+        }
+        compositions
+    }
+}
+
+impl Currency {
+    /// Everything known about the denomination.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::Currency;
+    /// use coin_changing::metal::Metal;
+    ///
+    /// let info = Currency::Florin.info();
+    /// assert_eq!(info.formal_name, "two shillings");
+    /// assert_eq!(info.first_year, Some(1849));
+    /// assert_eq!(info.last_year, None);
+    /// assert_eq!(info.slang, ["two bob bit"]);
+    /// assert_eq!(info.mass, Some(11_310));
+    /// let metals: Vec<(Option<u32>, Metal)> = info.compositions().iter().map(|(y, s)| (*y, s.metal)).collect();
+    /// assert_eq!(metals, [(None, Metal::Silver), (Some(1920), Metal::Silver), (Some(1947), Metal::Cupronickel)]);
+    /// ```
+    pub fn info(&self) -> CurrencyInfo {
+        let (formal_name, diameter, slang): (_, _, &[_]) = match self {
+            Currency::Farthing => ("farthing", Some(20_190), &[]),
+            Currency::Halfpenny => ("halfpenny", Some(25_480), &["ha'penny"]),
+            Currency::Penny => ("penny", Some(30_810), &["copper"]),
+            Currency::Threepence => ("threepence", Some(21_000), &["thruppenny bit"]),
+            Currency::Groat => ("fourpence", Some(16_000), &["joey"]),
+            Currency::Sixpence => ("sixpence", Some(19_410), &["tanner", "sprat"]),
+            Currency::Shilling => ("shilling", Some(23_600), &["bob"]),
+            Currency::Florin => ("two shillings", Some(28_500), &["two bob bit"]),
+            Currency::HalfCrown => ("half crown", Some(32_310), &["half a dollar"]),
+            Currency::DoubleFlorin => ("double florin", Some(36_000), &["barmaid's grief"]),
+            Currency::Crown => ("crown", Some(38_610), &["dollar"]),
+            Currency::HalfSovereign => ("half sovereign", Some(19_300), &["half sov"]),
+            Currency::TenShilling => ("ten shillings", None, &["ten bob note"]),
+            Currency::Sovereign => ("sovereign", Some(22_050), &["sov"]),
+            Currency::OnePound => ("one pound", None, &["quid", "nicker"]),
+            Currency::FivePound => ("five pounds", None, &["fiver"]),
+            Currency::TenPound => ("ten pounds", None, &["tenner"]),
+        };
+        let years = || (FIRST_YEAR..=LAST_YEAR).filter(|&year| era::in_circulation(*self, year));
+        let first_year = years().next().filter(|&year| year != FIRST_YEAR);
+        let last_year = years().next_back().filter(|&year| year != LAST_YEAR);
+        let mass = diameter.map(|_| self.spec(last_year.unwrap_or(LAST_YEAR)).milligrams);
+        CurrencyInfo { currency: *self, formal_name, first_year, last_year, diameter, mass, slang }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metal::Metal;
+
+    #[test]
+    fn info_agrees_with_the_rest_of_the_crate() {
+        for currency in Currency::ALL {
+            let info = currency.info();
+            assert_eq!(info.slang.first().copied(), currency.nickname());
+            assert_eq!(info.diameter.is_none(), currency.is_note());
+            assert_eq!(info.mass.is_none(), currency.is_note());
+            assert_eq!(info.compositions().last().unwrap().1, currency.spec(LAST_YEAR));
+        }
+        let double_florin = Currency::DoubleFlorin.info();
+        assert_eq!((double_florin.first_year, double_florin.last_year), (Some(1887), Some(1890)));
+        assert_eq!(double_florin.mass, Some(Currency::DoubleFlorin.spec(1890).milligrams));
+        // The last threepences were brass, not silver.
+        assert_eq!(Currency::Threepence.info().mass, Some(6_800));
+        let penny: Vec<Metal> = Currency::Penny.info().compositions().iter().map(|(_, s)| s.metal).collect();
+        assert_eq!(penny, [Metal::Copper, Metal::Bronze]);
+        assert!(!Currency::TenPound.info().in_circulation(1950));
+    }
+}
@@ -0,0 +1,306 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A shop's books: dated money in and out, with the balance after each entry.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::{balance::Balance, terminology::Terminology, Price};
+
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// A day in the calendar. Dates compare in time order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Panics if the month or day doesn't exist, e.g. the 30th of February.
+    pub fn new(year: u32, month: u8, day: u8) -> Self {
+        assert!((1..=12).contains(&month), "there's no month {}", month);
+        let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        assert!((1..=days).contains(&day), "there's no day {} in month {} of {}", day, month, year);
+        Date { year, month, day }
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// The month, from 1 for January to 12 for December.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+/// Written like "3 Mar 1952".
+impl Display for Date {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {} {}", self.day, MONTHS[self.month as usize - 1], self.year)
+    }
+}
+
+/// Which column of the books an entry goes in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// Money coming in, which adds to the balance.
+    Credit,
+    /// Money going out, which takes from the balance.
+    Debit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub date: Date,
+    pub description: String,
+    pub side: Side,
+    pub amount: Price,
+}
+
+impl Entry {
+    /// What the entry does to the balance: positive for a credit, negative for a debit.
+    pub fn change(&self) -> Balance {
+        match self.side {
+            Side::Credit => Balance::from(self.amount),
+            Side::Debit => Balance::debt(self.amount),
+        }
+    }
+}
+
+/// Dated credits and debits, kept in date order, starting from an opening balance. Entries on the
+/// same day stay in the order they were made.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::price;
+/// use coin_changing::balance::Balance;
+/// use coin_changing::ledger::{ Date, Ledger };
+///
+/// let mut books = Ledger::with_opening(Balance::from(price!(5/-/-)));
+/// books.debit(Date::new(1952, 3, 3), "Flour", price!(2/6));
+/// books.credit(Date::new(1952, 3, 1), "Takings", price!(1/4/-));
+/// assert_eq!(books.balance(), Balance::from(price!(6/1/6)));
+/// let balances: Vec<Balance> = books.running_balances().map(|(_, b)| b).collect();
+/// assert_eq!(balances, vec![Balance::from(price!(6/4/-)), Balance::from(price!(6/1/6))]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Ledger {
+    opening: Balance,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_opening(opening: Balance) -> Self {
+        Ledger { opening, entries: Vec::new() }
+    }
+
+    pub fn opening(&self) -> Balance {
+        self.opening
+    }
+
+    /// Every entry, in date order.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Record an entry, after any others on the same day.
+    pub fn record(&mut self, entry: Entry) {
+        let index = self.entries.partition_point(|e| e.date <= entry.date);
+        self.entries.insert(index, entry);
+    }
+
+    pub fn credit(&mut self, date: Date, description: impl Into<String>, amount: Price) {
+        self.record(Entry { date, description: description.into(), side: Side::Credit, amount });
+    }
+
+    pub fn debit(&mut self, date: Date, description: impl Into<String>, amount: Price) {
+        self.record(Entry { date, description: description.into(), side: Side::Debit, amount });
+    }
+
+    /// The balance after every entry.
+    pub fn balance(&self) -> Balance {
+        self.opening + self.entries.iter().map(Entry::change).sum::<Balance>()
+    }
+
+    /// The balance at the end of `date`, i.e. after every entry on or before it.
+    pub fn balance_on(&self, date: Date) -> Balance {
+        self.opening + self.entries.iter().take_while(|e| e.date <= date).map(Entry::change).sum::<Balance>()
+    }
+
+    /// Each entry along with the balance straight after it.
+    pub fn running_balances(&self) -> impl Iterator<Item = (&Entry, Balance)> + '_ {
+        self.entries.iter().scan(self.opening, |balance, entry| {
+            *balance += entry.change();
+            Some((entry, *balance))
+        })
+    }
+
+    /// The entries from `from` to `to`, including both.
+    pub fn between(&self, from: Date, to: Date) -> impl Iterator<Item = &Entry> + '_ {
+        self.entries.iter().filter(move |e| from <= e.date && e.date <= to)
+    }
+
+    /// A statement of the entries from `from` to `to` (including both), with the balance brought
+    /// forward from before and carried forward after.
+    pub fn statement(&self, from: Date, to: Date) -> Statement {
+        let brought_forward = self.opening
+            + self.entries.iter().take_while(|e| e.date < from).map(Entry::change).sum::<Balance>();
+        let lines: Vec<(Entry, Balance)> = self
+            .running_balances()
+            .filter(|(e, _)| from <= e.date && e.date <= to)
+            .map(|(e, b)| (e.clone(), b))
+            .collect();
+        let carried_forward = lines.last().map_or(brought_forward, |&(_, b)| b);
+        Statement { from, to, brought_forward, lines, carried_forward }
+    }
+}
+
+/// The entries in a [`Ledger`] over a stretch of time, as returned by [`Ledger::statement`].
+///
+/// Displaying it lays it out in columns, with debits marked "Dr", credits "Cr", and balances in
+/// debt in brackets.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::price;
+/// use coin_changing::ledger::{ Date, Ledger };
+///
+/// let mut books = Ledger::new();
+/// books.credit(Date::new(1952, 2, 28), "Takings", price!(1/-/-));
+/// books.debit(Date::new(1952, 3, 3), "Flour", price!(2/6));
+/// books.debit(Date::new(1952, 3, 14), "Coal", price!(1/-/-));
+/// let statement = books.statement(Date::new(1952, 3, 1), Date::new(1952, 3, 31));
+/// assert_eq!(statement.to_string(), "\
+/// Statement from 1 Mar 1952 to 31 Mar 1952
+///              Brought forward               £1 0s 0d
+///  3 Mar 1952  Flour            Dr £0 2s 6d  £0 17s 6d
+/// 14 Mar 1952  Coal             Dr £1 0s 0d  (£0 2s 6d)
+///              Carried forward               (£0 2s 6d)
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    pub from: Date,
+    pub to: Date,
+    pub brought_forward: Balance,
+    /// Each entry in the period along with the balance straight after it.
+    pub lines: Vec<(Entry, Balance)>,
+    pub carried_forward: Balance,
+}
+
+impl Statement {
+    /// Write the statement out in the words and date style of `terms`. [`Display`] does the same
+    /// with the default [`Terminology`].
+    pub fn render(&self, terms: &Terminology) -> String {
+        let mut out = String::new();
+        self.write(&mut out, terms).unwrap();
+        out
+    }
+
+    /// Like [`Statement::render`], but writing straight into `out`.
+    pub fn write(&self, out: &mut impl Write, terms: &Terminology) -> core::fmt::Result {
+        write!(out, "{} ", terms.statement_from)?;
+        terms.write_date(out, self.from)?;
+        write!(out, " {} ", terms.statement_to)?;
+        terms.write_date(out, self.to)?;
+        writeln!(out)?;
+        // Each row is a date, a description, an amount, and the balance after it.
+        let mut rows: Vec<(String, String, String, Balance)> = Vec::new();
+        rows.push((String::new(), String::from(terms.brought_forward), String::new(), self.brought_forward));
+        for (entry, balance) in &self.lines {
+            let (mut date, mut description) = (String::new(), String::new());
+            terms.write_date(&mut date, entry.date)?;
+            terms.write_description(&mut description, entry.side, &entry.description)?;
+            rows.push((date, description, format!("{} {}", terms.side(entry.side), entry.amount), *balance));
+        }
+        rows.push((String::new(), String::from(terms.carried_forward), String::new(), self.carried_forward));
+        let width = |column: fn(&(String, String, String, Balance)) -> &String| {
+            rows.iter().map(|r| column(r).chars().count()).max().unwrap_or(0)
+        };
+        let date_width = width(|r| &r.0);
+        let description_width = width(|r| &r.1);
+        let amount_width = width(|r| &r.2);
+        for (date, description, amount, balance) in &rows {
+            writeln!(
+                out,
+                "{:>dw$}  {:<nw$}  {:>aw$}  {:#}",
+                date,
+                description,
+                amount,
+                balance,
+                dw = date_width,
+                nw = description_width,
+                aw = amount_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write(f, &Terminology::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+    use alloc::string::ToString;
+
+    #[test]
+    fn dates_and_ranges() {
+        let mut books = Ledger::new();
+        books.credit(Date::new(1952, 1, 5), "a", price!(1/-));
+        books.credit(Date::new(1952, 1, 5), "b", price!(2/-));
+        books.credit(Date::new(1951, 12, 31), "c", price!(3/-));
+        let order: Vec<&str> = books.entries().iter().map(|e| e.description.as_str()).collect();
+        assert_eq!(order, ["c", "a", "b"]);
+        assert_eq!(books.between(Date::new(1952, 1, 1), Date::new(1952, 1, 5)).count(), 2);
+        assert_eq!(books.balance_on(Date::new(1952, 1, 4)), Balance::from(price!(3/-)));
+        let statement = books.statement(Date::new(1953, 1, 1), Date::new(1953, 12, 31));
+        assert!(statement.lines.is_empty());
+        assert_eq!(statement.carried_forward, Balance::from(price!(6/-)));
+        assert_eq!(Date::new(2000, 2, 29).to_string(), "29 Feb 2000");
+        let date = Date::new(1971, 2, 15);
+        assert_eq!((date.year(), date.month(), date.day()), (1971, 2, 15));
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_leap_day_in_1900() {
+        Date::new(1900, 2, 29);
+    }
+}
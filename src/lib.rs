@@ -12,22 +12,194 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{fmt::Display, iter::Sum};
+#![cfg_attr(not(feature = "std"), no_std)]
 
-const CURRENCIES_AS_HALFPENCE: [usize; 11] = [1, 2, 6, 12, 24, 48, 60, 120, 480, 2400, 4800];
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Sub, SubAssign},
+};
+
+use crate::era::CurrencySet;
+
+pub mod arena;
+pub mod assertions;
+pub mod balance;
+pub mod bank;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod breakdown;
+pub mod composition;
+pub mod daybook;
+pub mod debt;
+pub mod decimal;
+pub mod era;
+pub mod fare;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod info;
+pub mod ledger;
+pub mod metal;
+pub mod minor;
+pub mod notation;
+pub mod parse;
+pub mod percent;
+pub mod prelude;
+pub mod receipt;
+pub mod rounding;
+pub mod stats;
+pub mod tender;
+pub mod terminology;
+pub mod till;
+pub mod transaction;
+pub mod verify;
+pub mod wages;
+pub mod weighing;
+pub mod wholesale;
+pub mod words;
+
+/// Number of farthings in a halfpenny.
+pub const FARTHINGS_PER_HALFPENNY: usize = 2;
+/// Number of halfpence in a penny.
+pub const HALFPENCE_PER_PENNY: usize = 2;
+/// Number of pence in a shilling.
+pub const PENCE_PER_SHILLING: usize = 12;
+/// Number of shillings in a pound.
+pub const SHILLINGS_PER_POUND: usize = 20;
+/// Number of shillings in a guinea. The guinea coin itself was gone by 1814, but fees and luxury
+/// goods carried on being priced in guineas right up until decimalisation.
+pub const SHILLINGS_PER_GUINEA: usize = 21;
+/// Number of halfpence in a shilling.
+pub const HALFPENCE_PER_SHILLING: usize = HALFPENCE_PER_PENNY * PENCE_PER_SHILLING;
+/// Number of halfpence in a pound.
+pub const HALFPENCE_PER_POUND: usize = HALFPENCE_PER_SHILLING * SHILLINGS_PER_POUND;
+/// Number of farthings in a penny.
+pub const FARTHINGS_PER_PENNY: usize = FARTHINGS_PER_HALFPENNY * HALFPENCE_PER_PENNY;
+/// Number of farthings in a shilling.
+pub const FARTHINGS_PER_SHILLING: usize = FARTHINGS_PER_PENNY * PENCE_PER_SHILLING;
+/// Number of farthings in a pound.
+pub const FARTHINGS_PER_POUND: usize = FARTHINGS_PER_SHILLING * SHILLINGS_PER_POUND;
+
+/// Convert whole pence to halfpence.
+pub const fn pence_to_halfpence(pence: usize) -> usize {
+    pence * HALFPENCE_PER_PENNY
+}
+
+/// Convert whole shillings to halfpence.
+pub const fn shillings_to_halfpence(shillings: usize) -> usize {
+    shillings * HALFPENCE_PER_SHILLING
+}
+
+/// Convert whole pounds to halfpence.
+pub const fn pounds_to_halfpence(pounds: usize) -> usize {
+    pounds * HALFPENCE_PER_POUND
+}
+
+/// Split a halfpence value into whole pence and a leftover halfpenny (either 0 or 1).
+pub const fn halfpence_to_pence(halfpence: usize) -> (usize, usize) {
+    (halfpence / HALFPENCE_PER_PENNY, halfpence % HALFPENCE_PER_PENNY)
+}
+
+/// Convert halfpence to farthings.
+pub const fn halfpence_to_farthings(halfpence: usize) -> usize {
+    halfpence * FARTHINGS_PER_HALFPENNY
+}
+
+/// Convert whole pence to farthings.
+pub const fn pence_to_farthings(pence: usize) -> usize {
+    pence * FARTHINGS_PER_PENNY
+}
+
+/// Convert whole shillings to farthings.
+pub const fn shillings_to_farthings(shillings: usize) -> usize {
+    shillings * FARTHINGS_PER_SHILLING
+}
+
+/// Convert whole pounds to farthings.
+pub const fn pounds_to_farthings(pounds: usize) -> usize {
+    pounds * FARTHINGS_PER_POUND
+}
+
+/// Split a farthings value into whole pence and leftover farthings (between 0 and 3).
+pub const fn farthings_to_pence(farthings: usize) -> (usize, usize) {
+    (farthings / FARTHINGS_PER_PENNY, farthings % FARTHINGS_PER_PENNY)
+}
+
+const CURRENCIES_AS_FARTHINGS: [usize; 17] = [
+    1,
+    halfpence_to_farthings(1),
+    pence_to_farthings(1),
+    pence_to_farthings(3),
+    pence_to_farthings(4),
+    pence_to_farthings(6),
+    shillings_to_farthings(1),
+    shillings_to_farthings(2),
+    shillings_to_farthings(2) + pence_to_farthings(6),
+    shillings_to_farthings(4),
+    shillings_to_farthings(5),
+    shillings_to_farthings(10),
+    shillings_to_farthings(10),
+    pounds_to_farthings(1),
+    pounds_to_farthings(1),
+    pounds_to_farthings(5),
+    pounds_to_farthings(10),
+];
+
+/// Every [`Currency`], in the same order as `CURRENCIES_AS_FARTHINGS`.
+pub(crate) const CURRENCIES: [Currency; 17] = [
+    Currency::Farthing,
+    Currency::Halfpenny,
+    Currency::Penny,
+    Currency::Threepence,
+    Currency::Groat,
+    Currency::Sixpence,
+    Currency::Shilling,
+    Currency::Florin,
+    Currency::HalfCrown,
+    Currency::DoubleFlorin,
+    Currency::Crown,
+    Currency::HalfSovereign,
+    Currency::TenShilling,
+    Currency::Sovereign,
+    Currency::OnePound,
+    Currency::FivePound,
+    Currency::TenPound,
+];
 
 /// The currencies that were in use before decimalisation. Note, the crown wasn't used that much in
-/// real day-to-day life.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// real day-to-day life. They're ordered by value, smallest first.
+///
+/// Some denominations were only around for part of the period, and aren't given as change unless
+/// asked for; see [`Currency::is_extra`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Currency {
+    Farthing,
     Halfpenny,
     Penny,
     Threepence,
+    /// Fourpence. Only struck for circulation until 1855, though Maundy groats carried on.
+    Groat,
     Sixpence,
     Shilling,
     Florin,
     HalfCrown,
+    /// Four shillings. Only struck from 1887 to 1890, and never popular.
+    DoubleFlorin,
     Crown,
+    /// A gold ten shilling coin, in circulation until the First World War.
+    HalfSovereign,
+    /// The ten shilling note, or "ten bob note", which took over from the half sovereign.
+    TenShilling,
+    /// A gold pound coin, in circulation until the First World War.
+    Sovereign,
     OnePound,
     FivePound,
     TenPound,
@@ -36,35 +208,475 @@ pub enum Currency {
 impl Currency {
     /// Convert from halfpence value. For example, 48 gives [`Currency::Florin`]. If no currency
     /// matches the given halfpence value, [`None`] is returned.
-    pub fn from_halfpence(halfpence: usize) -> Option<Self> {
-        match halfpence {
-            1 => Some(Self::Halfpenny),
-            2 => Some(Self::Penny),
-            6 => Some(Self::Threepence),
-            12 => Some(Self::Sixpence),
-            24 => Some(Self::Shilling),
-            48 => Some(Self::Florin),
-            60 => Some(Self::HalfCrown),
-            120 => Some(Self::Crown),
-            480 => Some(Self::OnePound),
-            2400 => Some(Self::FivePound),
-            4800 => Some(Self::TenPound),
+    pub const fn from_halfpence(halfpence: usize) -> Option<Self> {
+        Self::from_farthings(halfpence_to_farthings(halfpence))
+    }
+
+    /// Convert from farthings value. For example, 96 gives [`Currency::Florin`]. If no currency
+    /// matches the given farthings value, [`None`] is returned. The gold coins share their values
+    /// with notes, and the notes are what's returned.
+    pub const fn from_farthings(farthings: usize) -> Option<Self> {
+        match farthings {
+            1 => Some(Self::Farthing),
+            2 => Some(Self::Halfpenny),
+            4 => Some(Self::Penny),
+            12 => Some(Self::Threepence),
+            16 => Some(Self::Groat),
+            24 => Some(Self::Sixpence),
+            48 => Some(Self::Shilling),
+            96 => Some(Self::Florin),
+            120 => Some(Self::HalfCrown),
+            192 => Some(Self::DoubleFlorin),
+            240 => Some(Self::Crown),
+            480 => Some(Self::TenShilling),
+            960 => Some(Self::OnePound),
+            4800 => Some(Self::FivePound),
+            9600 => Some(Self::TenPound),
             _ => None,
         }
     }
+
+    /// Every currency, smallest first.
+    pub const ALL: [Currency; 17] = CURRENCIES;
+
+    /// What the currency is worth. Unlike `Price::from`, this works in `const` items.
+    pub const fn value(&self) -> Price {
+        Price::from_farthings(self.value_in_farthings())
+    }
+
+    /// What the currency is worth in halfpence. The farthing is rounded down to nothing, so use
+    /// [`Currency::value_in_farthings`] for the exact value.
+    pub const fn value_in_halfpence(&self) -> usize {
+        self.value_in_farthings() / FARTHINGS_PER_HALFPENNY
+    }
+
+    /// What the currency is worth in farthings, the smallest unit, so this is always exact. For
+    /// example, a florin is 96.
+    pub const fn value_in_farthings(&self) -> usize {
+        CURRENCIES_AS_FARTHINGS[*self as usize]
+    }
+
+    /// Whether this is one of the denominations which were only around for part of the period:
+    /// the groat, double florin, half sovereign, ten shilling note, and sovereign. These are left
+    /// out of change unless [`ExtraPolicy::Included`] is used.
+    pub const fn is_extra(&self) -> bool {
+        matches!(
+            self,
+            Currency::Groat
+                | Currency::DoubleFlorin
+                | Currency::HalfSovereign
+                | Currency::TenShilling
+                | Currency::Sovereign
+        )
+    }
+
+    /// What one of this coin or note is called, e.g. "half crown". This is also how it's
+    /// displayed.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Currency::Farthing => "farthing",
+            Currency::Halfpenny => "halfpenny",
+            Currency::Penny => "penny",
+            Currency::Threepence => "threepenny bit",
+            Currency::Groat => "groat",
+            Currency::Sixpence => "sixpence",
+            Currency::Shilling => "shilling",
+            Currency::Florin => "florin",
+            Currency::HalfCrown => "half crown",
+            Currency::DoubleFlorin => "double florin",
+            Currency::Crown => "crown",
+            Currency::HalfSovereign => "half sovereign",
+            Currency::TenShilling => "ten shilling note",
+            Currency::Sovereign => "sovereign",
+            Currency::OnePound => "pound note",
+            Currency::FivePound => "five pound note",
+            Currency::TenPound => "ten pound note",
+        }
+    }
+
+    /// What more than one of this coin or note are called, e.g. "pennies". (A price in pennies
+    /// is in pence, but the coins themselves are pennies.)
+    pub fn plural(&self) -> &'static str {
+        match self {
+            Currency::Farthing => "farthings",
+            Currency::Halfpenny => "halfpennies",
+            Currency::Penny => "pennies",
+            Currency::Threepence => "threepenny bits",
+            Currency::Groat => "groats",
+            Currency::Sixpence => "sixpences",
+            Currency::Shilling => "shillings",
+            Currency::Florin => "florins",
+            Currency::HalfCrown => "half crowns",
+            Currency::DoubleFlorin => "double florins",
+            Currency::Crown => "crowns",
+            Currency::HalfSovereign => "half sovereigns",
+            Currency::TenShilling => "ten shilling notes",
+            Currency::Sovereign => "sovereigns",
+            Currency::OnePound => "pound notes",
+            Currency::FivePound => "five pound notes",
+            Currency::TenPound => "ten pound notes",
+        }
+    }
+
+    /// What the coin or note was called in everyday speech, if it had a common nickname.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::Currency;
+    ///
+    /// assert_eq!(Currency::Shilling.nickname(), Some("bob"));
+    /// assert_eq!(Currency::HalfCrown.nickname(), Some("half a dollar"));
+    /// assert_eq!(Currency::Farthing.nickname(), None);
+    /// assert_eq!(format!("3 {}, 1 {}", Currency::Shilling.plural(), Currency::Sixpence), "3 shillings, 1 sixpence");
+    /// ```
+    pub fn nickname(&self) -> Option<&'static str> {
+        match self {
+            Currency::Farthing => None,
+            Currency::Halfpenny => Some("ha'penny"),
+            Currency::Penny => Some("copper"),
+            Currency::Threepence => Some("thruppenny bit"),
+            Currency::Groat => Some("joey"),
+            Currency::Sixpence => Some("tanner"),
+            Currency::Shilling => Some("bob"),
+            Currency::Florin => Some("two bob bit"),
+            Currency::HalfCrown => Some("half a dollar"),
+            Currency::DoubleFlorin => Some("barmaid's grief"),
+            Currency::Crown => Some("dollar"),
+            Currency::HalfSovereign => Some("half sov"),
+            Currency::TenShilling => Some("ten bob note"),
+            Currency::Sovereign => Some("sov"),
+            Currency::OnePound => Some("quid"),
+            Currency::FivePound => Some("fiver"),
+            Currency::TenPound => Some("tenner"),
+        }
+    }
+
+    /// Whether this is a banknote rather than a coin.
+    pub const fn is_note(&self) -> bool {
+        matches!(self, Currency::TenShilling | Currency::OnePound | Currency::FivePound | Currency::TenPound)
+    }
+
+    /// Every way of making this coin or note exactly out of at most `limit_coins` smaller ones.
+    /// They're worked out one at a time as the iterator is used, since there are millions of ways
+    /// to make up a pound. Ways with bigger coins come first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Wallet };
+    ///
+    /// let mut ways = Currency::Florin.decompositions(3);
+    /// assert_eq!(ways.next(), Some(Wallet { shillings: 2, ..Default::default() }));
+    /// assert_eq!(ways.next(), Some(Wallet { shillings: 1, sixpence: 2, ..Default::default() }));
+    /// assert_eq!(ways.next(), None);
+    /// assert_eq!(Currency::Farthing.decompositions(10).next(), None);
+    /// ```
+    pub fn decompositions(&self, limit_coins: usize) -> Decompositions {
+        let worth = CURRENCIES_AS_FARTHINGS[*self as usize];
+        let smaller = CURRENCIES_AS_FARTHINGS.iter().take_while(|&&w| w < worth).count();
+        Decompositions { stack: vec![(smaller, worth, limit_coins, [0; 17])] }
+    }
 }
 
-/// Like a wallet, a container for various coins and notes.
+/// The ways of making up a coin or note from smaller ones, made by [`Currency::decompositions`].
+#[derive(Debug, Clone)]
+pub struct Decompositions {
+    /// Partial ways still to look at: how many denominations are left to pick counts for, how
+    /// much is still to be made, how many more coins can be used, and the coins so far.
+    stack: Vec<(usize, usize, usize, [usize; 17])>,
+}
+
+impl Iterator for Decompositions {
+    type Item = Wallet;
+
+    fn next(&mut self) -> Option<Wallet> {
+        while let Some((left, remaining, coins_left, counts)) = self.stack.pop() {
+            if remaining == 0 {
+                return Some(Wallet::from_counts(counts));
+            }
+            let Some(i) = left.checked_sub(1) else { continue };
+            let worth = CURRENCIES_AS_FARTHINGS[i];
+            // Even using only this denomination, there aren't enough coins left to get there.
+            if remaining.div_ceil(worth) > coins_left {
+                continue;
+            }
+            let most = (remaining / worth).min(coins_left);
+            // Pushed smallest count first, so the most of this denomination is looked at first.
+            for count in 0..=most {
+                let mut counts = counts;
+                counts[i] = count;
+                self.stack.push((i, remaining - count * worth, coins_left - count, counts));
+            }
+        }
+        None
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// How crowns are treated when making change and taking payment. Since crowns were mostly struck
+/// as commemoratives rather than spent, it's often more realistic to never hand them out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrownPolicy {
+    /// Crowns are like any other coin.
+    #[default]
+    Circulating,
+    /// Crowns are accepted at face value but never given as change.
+    Commemorative,
+    /// Crowns are never given as change and aren't accepted as payment either.
+    Refused,
+}
+
+impl CrownPolicy {
+    /// The farthings values of the denominations which may be given as change under this policy.
+    pub fn change_denominations(&self) -> Vec<usize> {
+        ChangePolicy::from(*self).change_denominations()
+    }
+
+    /// Whether crowns may be tendered as payment under this policy.
+    pub fn accepts_crowns(&self) -> bool {
+        *self != CrownPolicy::Refused
+    }
+}
+
+/// Whether the halfpenny can be used. It was demonetised at the end of July 1969, after which
+/// amounts had to be in whole pence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HalfpennyPolicy {
+    #[default]
+    Circulating,
+    /// Amounts are rounded to whole pence, halfpennies are never given as change, and they aren't
+    /// accepted as payment.
+    Withdrawn,
+}
+
+impl HalfpennyPolicy {
+    /// The policy in force for most of the given year. The halfpenny counts as withdrawn from
+    /// 1970 onwards.
+    pub fn for_year(year: u32) -> Self {
+        if year >= 1970 {
+            HalfpennyPolicy::Withdrawn
+        } else {
+            HalfpennyPolicy::Circulating
+        }
+    }
+
+    /// Round a price to what can be paid under this policy. Once the halfpenny is withdrawn, any
+    /// fraction of a penny is rounded up to the next penny, as shops did at the time.
+    pub fn round(&self, price: Price) -> Price {
+        let farthings = price.to_farthings();
+        match self {
+            HalfpennyPolicy::Circulating => price,
+            HalfpennyPolicy::Withdrawn => {
+                let (pence, fraction) = farthings_to_pence(farthings);
+                Price::from_pence(pence + (fraction > 0) as usize)
+            }
+        }
+    }
+
+    /// Like [`HalfpennyPolicy::round`], but also reporting what the rounding did.
+    pub fn round_with_report(&self, price: Price) -> (Price, rounding::RoundingReport) {
+        let rounded = self.round(price);
+        let rule = match self {
+            HalfpennyPolicy::Circulating => "halfpenny circulating, no rounding",
+            HalfpennyPolicy::Withdrawn => "halfpenny withdrawn, round up to the penny",
+        };
+        (rounded, rounding::RoundingReport::new(rule, price, rounded))
+    }
+
+    /// Format a price for display under this policy, i.e. [`HalfpennyPolicy::round`] and then
+    /// [`Display`].
+    pub fn format(&self, price: Price) -> String {
+        self.round(price).to_string()
+    }
+}
+
+/// Whether the denominations which weren't around for the whole period (see
+/// [`Currency::is_extra`]) are given as change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtraPolicy {
+    #[default]
+    Excluded,
+    Included,
+}
+
+/// Which coins and notes are allowed when making change and checking payments. Each kind of
+/// policy converts into this, so anything taking a `impl Into<ChangePolicy>` can be given just a
+/// [`CrownPolicy`], [`HalfpennyPolicy`], [`ExtraPolicy`] or [`CurrencySet`] too.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangePolicy {
+    pub crowns: CrownPolicy,
+    pub halfpennies: HalfpennyPolicy,
+    pub extras: ExtraPolicy,
+    /// Denominations outside this set are never given as change, nor accepted as payment.
+    pub currencies: CurrencySet,
+    /// Denominations which are accepted as payment but never given as change, e.g. because
+    /// nobody wanted them any more. Empty by default.
+    pub avoided: CurrencySet,
+    /// Which threepenny bit to give as change while both the silver and brass ones were about,
+    /// from 1937 to 1945, or [`None`] for no preference. See [`ChangePolicy::spec`].
+    pub threepence: Option<metal::Metal>,
+}
+
+impl Default for ChangePolicy {
+    fn default() -> Self {
+        ChangePolicy {
+            crowns: CrownPolicy::default(),
+            halfpennies: HalfpennyPolicy::default(),
+            extras: ExtraPolicy::default(),
+            currencies: CurrencySet::all(),
+            avoided: CurrencySet::empty(),
+            threepence: None,
+        }
+    }
+}
+
+impl ChangePolicy {
+    /// The policy in force for most of the given year: only the coins and notes in circulation
+    /// (see [`CurrencySet::for_year`]), including the extras, and with the halfpenny following
+    /// [`HalfpennyPolicy::for_year`].
+    pub fn for_year(year: u32) -> Self {
+        ChangePolicy {
+            halfpennies: HalfpennyPolicy::for_year(year),
+            ..CurrencySet::for_year(year).into()
+        }
+    }
+
+    /// Round a price to what change can be given for under this policy: first with
+    /// [`HalfpennyPolicy::round`], then up to a whole number of the smallest denomination allowed,
+    /// e.g. to the halfpenny once the farthing has gone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ ChangePolicy, Price, Wallet };
+    ///
+    /// let farthing = Price::from_farthings(1);
+    /// assert_eq!(ChangePolicy::for_year(1950).round(farthing), farthing);
+    /// assert_eq!(ChangePolicy::for_year(1961).round(farthing), Price::from_halfpence(1));
+    /// assert_eq!(Wallet::change_for(farthing, ChangePolicy::for_year(1961)).halfpence, 1);
+    /// assert_eq!(ChangePolicy::for_year(1961).round_with_report(farthing).1.amount, farthing);
+    /// ```
+    pub fn round(&self, price: Price) -> Price {
+        let price = self.halfpennies.round(price);
+        match CURRENCIES.iter().find(|&&c| self.allows(c)) {
+            Some(&smallest) => price.round_to(smallest, rounding::RoundingMode::Up),
+            None => price,
+        }
+    }
+
+    /// Like [`ChangePolicy::round`], but also reporting what the rounding did.
+    pub fn round_with_report(&self, price: Price) -> (Price, rounding::RoundingReport) {
+        let rounded = self.round(price);
+        (rounded, rounding::RoundingReport::new("round up to the smallest coin allowed", price, rounded))
+    }
+
+    /// Whether `currency` may be given as change under this policy. Farthings go along with
+    /// halfpennies, since they'd been withdrawn even earlier.
+    pub fn allows(&self, currency: Currency) -> bool {
+        let crowns = self.crowns == CrownPolicy::Circulating || currency != Currency::Crown;
+        let halfpennies = self.halfpennies == HalfpennyPolicy::Circulating
+            || !matches!(currency, Currency::Farthing | Currency::Halfpenny);
+        let extras = self.extras == ExtraPolicy::Included || !currency.is_extra();
+        crowns && halfpennies && extras && self.currencies.contains(currency) && !self.avoided.contains(currency)
+    }
+
+    /// Change the way a shop assistant in the given year would most likely have given it, going
+    /// beyond [`ChangePolicy::for_year`]: crowns are kept back as keepsakes, the unpopular double
+    /// florin isn't handed out, farthings are avoided after 1956 when they'd stopped being worth
+    /// the bother, and the brass threepence is given rather than the silver one from 1942, when
+    /// the silver one stopped being struck.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ ChangePolicy, Price, Wallet, price };
+    ///
+    /// let change = Wallet::change_for(price!(5/-), ChangePolicy::plausible_for_year(1935));
+    /// assert_eq!(change, Wallet { half_crowns: 2, ..Default::default() });
+    /// ```
+    pub fn plausible_for_year(year: u32) -> Self {
+        let threepence = if year >= 1942 { metal::Metal::NickelBrass } else { metal::Metal::Silver };
+        let mut policy = ChangePolicy {
+            crowns: CrownPolicy::Commemorative,
+            threepence: Some(threepence),
+            ..Self::for_year(year)
+        };
+        policy.avoided.insert(Currency::DoubleFlorin);
+        if year > 1956 {
+            policy.avoided.insert(Currency::Farthing);
+        }
+        policy
+    }
+
+    /// Ready-made policies for each decade from the 1850s to the 1960s, using
+    /// [`ChangePolicy::plausible_for_year`] for the middle of the decade, for picking from a list.
+    pub fn decade_presets() -> Vec<(u32, ChangePolicy)> {
+        (1850..=1960).step_by(10).map(|decade| (decade, Self::plausible_for_year(decade + 5))).collect()
+    }
+
+    /// The farthings values of the denominations which may be given as change under this policy,
+    /// smallest first. Coins and notes of the same value only appear once.
+    pub fn change_denominations(&self) -> Vec<usize> {
+        let mut denominations: Vec<usize> = CURRENCIES
+            .iter()
+            .zip(CURRENCIES_AS_FARTHINGS)
+            .filter(|(&c, _)| self.allows(c))
+            .map(|(_, worth)| worth)
+            .collect();
+        denominations.dedup();
+        denominations
+    }
+}
+
+impl From<CrownPolicy> for ChangePolicy {
+    fn from(crowns: CrownPolicy) -> Self {
+        ChangePolicy { crowns, ..Default::default() }
+    }
+}
+
+impl From<HalfpennyPolicy> for ChangePolicy {
+    fn from(halfpennies: HalfpennyPolicy) -> Self {
+        ChangePolicy { halfpennies, ..Default::default() }
+    }
+}
+
+impl From<ExtraPolicy> for ChangePolicy {
+    fn from(extras: ExtraPolicy) -> Self {
+        ChangePolicy { extras, ..Default::default() }
+    }
+}
+
+/// Choosing a set of currencies means every one of them may be used, so the extras in the set are
+/// included.
+impl From<CurrencySet> for ChangePolicy {
+    fn from(currencies: CurrencySet) -> Self {
+        ChangePolicy { currencies, extras: ExtraPolicy::Included, ..Default::default() }
+    }
+}
+
+/// Like a wallet, a container for various coins and notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct Wallet {
+    pub farthings: usize,
     pub halfpence: usize,
     pub pennies: usize,
     pub threepence: usize,
+    pub groats: usize,
     pub sixpence: usize,
     pub shillings: usize,
     pub florins: usize,
     pub half_crowns: usize,
+    pub double_florins: usize,
     pub crowns: usize,
+    pub half_sovereigns: usize,
+    pub ten_shillings: usize,
+    pub sovereigns: usize,
     pub one_pounds: usize,
     pub five_pounds: usize,
     pub ten_pounds: usize,
@@ -73,135 +685,1122 @@ pub struct Wallet {
 impl Wallet {
     /// Add a coin or note to the wallet.
     pub fn add_currency(&mut self, currency: Currency) {
+        self[currency] += 1;
+    }
+
+    /// Remove a coin or note from the wallet. Panics if there isn't one to remove; see
+    /// [`Wallet::try_remove_currency`] for a version which doesn't.
+    pub fn remove_currency(&mut self, currency: Currency) {
+        if let Err(error) = self.try_remove_currency(currency) {
+            panic!("couldn't remove a coin: {}", error);
+        }
+    }
+
+    /// Remove a coin or note from the wallet, or leave the wallet alone and return an error if
+    /// there isn't one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Wallet, WalletError };
+    ///
+    /// let mut purse = Wallet { pennies: 1, ..Default::default() };
+    /// assert_eq!(purse.try_remove_currency(Currency::Penny), Ok(()));
+    /// let error = WalletError::NotEnough { currency: Currency::Penny, short: 1 };
+    /// assert_eq!(purse.try_remove_currency(Currency::Penny), Err(error));
+    /// assert_eq!(purse, Wallet::default());
+    /// ```
+    pub fn try_remove_currency(&mut self, currency: Currency) -> Result<(), WalletError> {
+        match self[currency].checked_sub(1) {
+            Some(count) => self[currency] = count,
+            None => return Err(WalletError::NotEnough { currency, short: 1 }),
+        }
+        Ok(())
+    }
+
+    /// The count of each denomination, in the same order as `CURRENCIES_AS_FARTHINGS`.
+    pub(crate) fn to_counts(self) -> [usize; 17] {
+        [
+            self.farthings,
+            self.halfpence,
+            self.pennies,
+            self.threepence,
+            self.groats,
+            self.sixpence,
+            self.shillings,
+            self.florins,
+            self.half_crowns,
+            self.double_florins,
+            self.crowns,
+            self.half_sovereigns,
+            self.ten_shillings,
+            self.sovereigns,
+            self.one_pounds,
+            self.five_pounds,
+            self.ten_pounds,
+        ]
+    }
+
+    /// The inverse of [`Wallet::to_counts`].
+    pub(crate) fn from_counts(counts: [usize; 17]) -> Self {
+        let [
+            farthings,
+            halfpence,
+            pennies,
+            threepence,
+            groats,
+            sixpence,
+            shillings,
+            florins,
+            half_crowns,
+            double_florins,
+            crowns,
+            half_sovereigns,
+            ten_shillings,
+            sovereigns,
+            one_pounds,
+            five_pounds,
+            ten_pounds,
+        ] = counts;
+        Wallet {
+            farthings,
+            halfpence,
+            pennies,
+            threepence,
+            groats,
+            sixpence,
+            shillings,
+            florins,
+            half_crowns,
+            double_florins,
+            crowns,
+            half_sovereigns,
+            ten_shillings,
+            sovereigns,
+            one_pounds,
+            five_pounds,
+            ten_pounds,
+        }
+    }
+
+    /// Get the halfpence value of the wallet. An odd farthing is rounded down, so use
+    /// [`Wallet::to_farthings`] for the exact value.
+    pub fn to_halfpence(&self) -> usize {
+        self.to_farthings() / FARTHINGS_PER_HALFPENNY
+    }
+
+    /// Get the farthings value of the wallet.
+    pub fn to_farthings(&self) -> usize {
+        self.to_counts()
+            .iter()
+            .zip(CURRENCIES_AS_FARTHINGS.iter())
+            .map(|(count, worth)| count * worth)
+            .sum()
+    }
+}
+
+/// How many of a coin or note the wallet holds, for looping over denominations without naming
+/// each field.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Currency, Wallet };
+///
+/// let mut purse = Wallet { florins: 2, ..Default::default() };
+/// purse[Currency::Florin] += 1;
+/// purse[Currency::Penny] = 6;
+/// assert_eq!(purse, Wallet { florins: 3, pennies: 6, ..Default::default() });
+/// assert_eq!(purse[Currency::Shilling], 0);
+/// ```
+impl Index<Currency> for Wallet {
+    type Output = usize;
+
+    fn index(&self, currency: Currency) -> &usize {
         match currency {
-            Currency::Halfpenny => self.halfpence += 1,
-            Currency::Penny => self.pennies += 1,
-            Currency::Threepence => self.threepence += 1,
-            Currency::Sixpence => self.sixpence += 1,
-            Currency::Shilling => self.shillings += 1,
-            Currency::Florin => self.florins += 1,
-            Currency::HalfCrown => self.half_crowns += 1,
-            Currency::Crown => self.crowns += 1,
-            Currency::OnePound => self.one_pounds += 1,
-            Currency::FivePound => self.five_pounds += 1,
-            Currency::TenPound => self.ten_pounds += 1,
+            Currency::Farthing => &self.farthings,
+            Currency::Halfpenny => &self.halfpence,
+            Currency::Penny => &self.pennies,
+            Currency::Threepence => &self.threepence,
+            Currency::Groat => &self.groats,
+            Currency::Sixpence => &self.sixpence,
+            Currency::Shilling => &self.shillings,
+            Currency::Florin => &self.florins,
+            Currency::HalfCrown => &self.half_crowns,
+            Currency::DoubleFlorin => &self.double_florins,
+            Currency::Crown => &self.crowns,
+            Currency::HalfSovereign => &self.half_sovereigns,
+            Currency::TenShilling => &self.ten_shillings,
+            Currency::Sovereign => &self.sovereigns,
+            Currency::OnePound => &self.one_pounds,
+            Currency::FivePound => &self.five_pounds,
+            Currency::TenPound => &self.ten_pounds,
         }
     }
+}
 
-    /// Remove a coin or note to the wallet.
-    pub fn remove_currency(&mut self, currency: Currency) {
+impl IndexMut<Currency> for Wallet {
+    fn index_mut(&mut self, currency: Currency) -> &mut usize {
         match currency {
-            Currency::Halfpenny => self.halfpence -= 1,
-            Currency::Penny => self.pennies -= 1,
-            Currency::Threepence => self.threepence -= 1,
-            Currency::Sixpence => self.sixpence -= 1,
-            Currency::Shilling => self.shillings -= 1,
-            Currency::Florin => self.florins -= 1,
-            Currency::HalfCrown => self.half_crowns -= 1,
-            Currency::Crown => self.crowns -= 1,
-            Currency::OnePound => self.one_pounds -= 1,
-            Currency::FivePound => self.five_pounds -= 1,
-            Currency::TenPound => self.ten_pounds -= 1,
+            Currency::Farthing => &mut self.farthings,
+            Currency::Halfpenny => &mut self.halfpence,
+            Currency::Penny => &mut self.pennies,
+            Currency::Threepence => &mut self.threepence,
+            Currency::Groat => &mut self.groats,
+            Currency::Sixpence => &mut self.sixpence,
+            Currency::Shilling => &mut self.shillings,
+            Currency::Florin => &mut self.florins,
+            Currency::HalfCrown => &mut self.half_crowns,
+            Currency::DoubleFlorin => &mut self.double_florins,
+            Currency::Crown => &mut self.crowns,
+            Currency::HalfSovereign => &mut self.half_sovereigns,
+            Currency::TenShilling => &mut self.ten_shillings,
+            Currency::Sovereign => &mut self.sovereigns,
+            Currency::OnePound => &mut self.one_pounds,
+            Currency::FivePound => &mut self.five_pounds,
+            Currency::TenPound => &mut self.ten_pounds,
+        }
+    }
+}
+
+impl Wallet {
+    /// Take another wallet's coins out of this one, or say which denomination (the smallest, if
+    /// there's more than one) there weren't enough of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Wallet, WalletError };
+    ///
+    /// let till = Wallet { pennies: 10, florins: 2, ..Default::default() };
+    /// let float = Wallet { pennies: 4, florins: 2, ..Default::default() };
+    /// assert_eq!(till.try_sub(float), Ok(Wallet { pennies: 6, ..Default::default() }));
+    /// let short = Wallet { florins: 3, ..Default::default() };
+    /// assert_eq!(till.try_sub(short), Err(WalletError::NotEnough { currency: Currency::Florin, short: 1 }));
+    /// ```
+    pub fn try_sub(&self, rhs: Wallet) -> Result<Wallet, WalletError> {
+        let (left, right) = (self.to_counts(), rhs.to_counts());
+        if let Some(i) = (0..CURRENCIES.len()).find(|&i| left[i] < right[i]) {
+            return Err(WalletError::NotEnough { currency: CURRENCIES[i], short: right[i] - left[i] });
+        }
+        Ok(Wallet::from_counts(core::array::from_fn(|i| left[i] - right[i])))
+    }
+
+    /// Take another wallet's coins out of this one, or [`None`] if it doesn't hold them all.
+    pub fn checked_sub(&self, rhs: Wallet) -> Option<Wallet> {
+        self.try_sub(rhs).ok()
+    }
+
+    /// The number of coins and notes in the wallet.
+    pub fn len(&self) -> usize {
+        self.to_counts().iter().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of coins in the wallet, not counting notes.
+    pub fn coin_count(&self) -> usize {
+        self.iter().filter(|(c, _)| !c.is_note()).map(|(_, count)| count).sum()
+    }
+
+    /// The number of notes in the wallet.
+    pub fn note_count(&self) -> usize {
+        self.iter().filter(|(c, _)| c.is_note()).map(|(_, count)| count).sum()
+    }
+
+    /// The most valuable denomination in the wallet, or [`None`] if it's empty.
+    pub fn largest(&self) -> Option<Currency> {
+        self.iter().last().map(|(c, _)| c)
+    }
+
+    /// The least valuable denomination in the wallet, or [`None`] if it's empty.
+    pub fn smallest(&self) -> Option<Currency> {
+        self.iter().next().map(|(c, _)| c)
+    }
+
+    /// How many of each denomination the wallet holds, leaving out the ones it has none of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Wallet };
+    ///
+    /// let purse = Wallet { pennies: 3, florins: 1, one_pounds: 2, ..Default::default() };
+    /// assert_eq!((purse.len(), purse.coin_count(), purse.note_count()), (6, 4, 2));
+    /// assert_eq!((purse.smallest(), purse.largest()), (Some(Currency::Penny), Some(Currency::OnePound)));
+    /// assert_eq!(purse.breakdown().get(&Currency::Florin), Some(&1));
+    /// assert_eq!(purse.breakdown().get(&Currency::Crown), None);
+    /// ```
+    pub fn breakdown(&self) -> BTreeMap<Currency, usize> {
+        self.iter().collect()
+    }
+
+    /// How many of each denomination the wallet holds, smallest first, skipping any it has none
+    /// of. Use [`IntoIterator`] instead to go through every coin and note one at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Wallet };
+    ///
+    /// let purse = Wallet { pennies: 3, florins: 1, ..Default::default() };
+    /// let held: Vec<(Currency, usize)> = purse.iter().collect();
+    /// assert_eq!(held, vec![(Currency::Penny, 3), (Currency::Florin, 1)]);
+    ///
+    /// let coins: Vec<Currency> = purse.into_iter().collect();
+    /// assert_eq!(coins, vec![Currency::Penny, Currency::Penny, Currency::Penny, Currency::Florin]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Currency, usize)> + '_ {
+        CURRENCIES.iter().map(|&c| (c, self[c])).filter(|&(_, count)| count > 0)
+    }
+}
+
+/// Every coin and note in a wallet one at a time, smallest first. Made by [`Wallet::into_iter`].
+#[derive(Debug, Clone)]
+pub struct Coins {
+    counts: [usize; 17],
+    index: usize,
+}
+
+impl Iterator for Coins {
+    type Item = Currency;
+
+    fn next(&mut self) -> Option<Currency> {
+        while self.counts.get(self.index)? == &0 {
+            self.index += 1;
+        }
+        self.counts[self.index] -= 1;
+        Some(CURRENCIES[self.index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.counts.iter().sum();
+        (left, Some(left))
+    }
+}
+
+impl ExactSizeIterator for Coins {}
+
+impl IntoIterator for Wallet {
+    type Item = Currency;
+    type IntoIter = Coins;
+
+    fn into_iter(self) -> Coins {
+        Coins { counts: self.to_counts(), index: 0 }
+    }
+}
+
+/// Put a load of coins and notes in a wallet.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Currency, Wallet };
+///
+/// let mut wallet: Wallet = [Currency::Penny, Currency::Shilling, Currency::Penny].into_iter().collect();
+/// assert_eq!(wallet, Wallet { pennies: 2, shillings: 1, ..Default::default() });
+/// wallet.extend([Currency::Crown]);
+/// assert_eq!(wallet.crowns, 1);
+/// assert_eq!(Wallet::from(&[Currency::Florin, Currency::Florin][..]).florins, 2);
+/// ```
+impl Extend<Currency> for Wallet {
+    fn extend<T: IntoIterator<Item = Currency>>(&mut self, iter: T) {
+        for currency in iter {
+            self.add_currency(currency);
+        }
+    }
+}
+
+impl FromIterator<Currency> for Wallet {
+    fn from_iter<T: IntoIterator<Item = Currency>>(iter: T) -> Self {
+        let mut wallet = Wallet::default();
+        wallet.extend(iter);
+        wallet
+    }
+}
+
+impl From<&[Currency]> for Wallet {
+    fn from(value: &[Currency]) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+impl Wallet {
+    /// Make change for a price using as few coins and notes as possible, only using the
+    /// denominations allowed by the given policy. The price is rounded first with
+    /// [`ChangePolicy::round`], e.g. if the halfpenny has been withdrawn.
+    pub fn change_for(price: Price, policy: impl Into<ChangePolicy>) -> Self {
+        let policy = policy.into();
+        let target = policy.round(price).to_farthings();
+        Self::from_farthing_coins(&coin_change(&policy.change_denominations(), target))
+    }
+
+    /// Build a wallet from a list of coins given as farthings values, e.g. the output of
+    /// [`coin_change`]. Panics if a value isn't a real denomination.
+    pub(crate) fn from_farthing_coins(coins: &[usize]) -> Self {
+        breakdown::ChangeBreakdown::from_farthing_coins(coins).expect("not a real denomination").into()
+    }
+}
+
+impl Wallet {
+    /// Whether some of the coins in this wallet add up to exactly `price`.
+    pub fn can_pay_exactly(&self, price: Price) -> bool {
+        self.exact_payment(price).is_some()
+    }
+
+    /// The coins to hand over to pay exactly `price` without needing change, or [`None`] if the
+    /// wallet's coins can't make it or it's over [`DEFAULT_MAX_TARGET`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, Wallet, price };
+    ///
+    /// let purse = Wallet { sixpence: 1, threepence: 3, ..Default::default() };
+    /// assert_eq!(purse.exact_payment(price!(-/9)).map(|w| w.to_farthings()), Some(36));
+    /// assert!(!purse.can_pay_exactly(price!(-/4)));
+    /// ```
+    pub fn exact_payment(&self, price: Price) -> Option<Wallet> {
+        let target = price.to_farthings();
+        if target > self.to_farthings() || target > DEFAULT_MAX_TARGET {
+            return None;
+        }
+        let table = CoinTable::new(self, target, false);
+        table.count(target).map(|_| table.coins(target))
+    }
+
+    /// The most this wallet can pay, without going over `bound`, using exactly the coins it holds
+    /// so that no change is needed. Returns the amount along with the coins to hand over. Amounts
+    /// over [`DEFAULT_MAX_TARGET`] aren't considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, Wallet, price };
+    ///
+    /// let wallet = Wallet { florins: 1, sixpence: 1, threepence: 1, ..Default::default() };
+    /// let (amount, coins) = wallet.max_payable_without_change(price!(2/8));
+    /// assert_eq!(amount, price!(2/6));
+    /// assert_eq!(coins, Wallet { florins: 1, sixpence: 1, ..Default::default() });
+    /// ```
+    pub fn max_payable_without_change(&self, bound: Price) -> (Price, Wallet) {
+        let bound = bound.to_farthings().min(self.to_farthings()).min(DEFAULT_MAX_TARGET);
+        let table = CoinTable::new(self, bound, false);
+        let best = (0..=bound).rev().find(|&t| table.count(t).is_some()).unwrap();
+        (Price::from_farthings(best), table.coins(best))
+    }
+}
+
+impl Wallet {
+    /// Make change for a price using as few coins and notes as possible, but only those held in
+    /// this wallet. Fails if the wallet can't make the amount exactly, or with
+    /// [`ChangeError::TooLarge`] if it's over [`DEFAULT_MAX_TARGET`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ ChangeError, Price, Wallet, price };
+    ///
+    /// let purse = Wallet { florins: 1, sixpence: 3, pennies: 2, ..Default::default() };
+    /// assert_eq!(purse.make_change(price!(3/-)), Ok(Wallet { florins: 1, sixpence: 2, ..Default::default() }));
+    /// assert_eq!(purse.make_change(price!(-/3)), Err(ChangeError::Unreachable { target: 12 }));
+    /// ```
+    pub fn make_change(&self, price: Price) -> Result<Self, ChangeError> {
+        let target = price.to_farthings();
+        if target > DEFAULT_MAX_TARGET {
+            return Err(ChangeError::TooLarge { target, max: DEFAULT_MAX_TARGET });
+        }
+        self.change_preserving(price, 0, ExtraPolicy::Included)
+            .ok_or(ChangeError::Unreachable { target: price.to_farthings() })
+    }
+
+    /// Make change for a price out of the coins in this wallet, e.g. a till's float. As with
+    /// [`Wallet::change_for`], as few coins as possible are used, but among the ways of doing that
+    /// the one leaving the fewest denominations under `threshold` coins is picked, so a till
+    /// doesn't run out of pennies an hour into the day. Returns [`None`] if the coins held can't
+    /// make the amount at all, or if it's over [`DEFAULT_MAX_TARGET`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ CrownPolicy, Price, Wallet, price };
+    ///
+    /// // Two sixpences would keep the last shilling, but fewer coins comes first.
+    /// let float = Wallet { shillings: 1, sixpence: 5, ..Default::default() };
+    /// let change = float.change_preserving(price!(1/-), 1, CrownPolicy::default());
+    /// assert_eq!(change, Some(Wallet { shillings: 1, ..Default::default() }));
+    /// ```
+    pub fn change_preserving(&self, price: Price, threshold: usize, policy: impl Into<ChangePolicy>) -> Option<Self> {
+        let policy = policy.into();
+        let target = policy.round(price).to_farthings();
+        if target > DEFAULT_MAX_TARGET {
+            return None;
+        }
+        let table = CoinTable::preserving(self, target, threshold, |c| policy.allows(c));
+        table.count(target)?;
+        Some(table.coins(target))
+    }
+}
+
+/// For every value up to some bound, the fewest (or most) coins from a wallet adding up to it
+/// exactly, along with which coins they are. Used by [`Wallet::pay`] and
+/// [`Wallet::change_preserving`], and by [`Transaction`](transaction::Transaction).
+///
+/// Each denomination held is one pass of a bounded knapsack, using a sliding window so a pass
+/// takes time in proportion to the bound however many coins are held. Only multiples of the
+/// greatest common divisor of the denominations held can be made, so only those are kept: a
+/// wallet of ten pound notes needs a thousand entries for £10,000 rather than millions.
+pub(crate) struct CoinTable {
+    /// Every value which can be made is a multiple of this many farthings.
+    step: usize,
+    /// Added to the cost for every coin used: [`COIN_COST`] when fewest coins are wanted, and
+    /// minus that when most.
+    per_coin: i64,
+    /// `costs[t / step]` is `per_coin` times the number of coins making `t` farthings, plus the
+    /// denominations run down below a threshold doing it, or [`UNREACHABLE`] if it can't be made.
+    costs: Vec<i64>,
+    /// For each denomination held, its index into `CURRENCIES` and how many are used for each
+    /// value, once denominations after it have been accounted for.
+    taken: Vec<(usize, Vec<u32>)>,
+}
+
+/// The cost in a [`CoinTable`] of a value which can't be made.
+const UNREACHABLE: i64 = i64::MAX;
+
+/// The cost in a [`CoinTable`] of each coin. Running a denomination down below a threshold costs
+/// 1, and there are fewer denominations than this, so comparing costs compares coins first.
+const COIN_COST: i64 = 32;
+
+impl CoinTable {
+    /// Work out the table for `wallet` up to `bound` farthings, preferring as many coins as
+    /// possible if `most` is set, and as few as possible otherwise. Panics if `bound` is over
+    /// [`DEFAULT_MAX_TARGET`].
+    pub(crate) fn new(wallet: &Wallet, bound: usize, most: bool) -> Self {
+        Self::build(wallet, bound, most, 0, |_| true)
+    }
+
+    /// Like [`CoinTable::new`] for the fewest coins, using only the denominations `allows`
+    /// permits, and among the ways of using fewest coins, preferring those which leave the fewest
+    /// denominations under `threshold` coins.
+    fn preserving(wallet: &Wallet, bound: usize, threshold: usize, allows: impl Fn(Currency) -> bool) -> Self {
+        Self::build(wallet, bound, false, threshold, allows)
+    }
+
+    fn build(wallet: &Wallet, bound: usize, most: bool, threshold: usize, allows: impl Fn(Currency) -> bool) -> Self {
+        assert!(bound <= DEFAULT_MAX_TARGET, "a coin table up to {} farthings is too big", bound);
+        let counts = wallet.to_counts();
+        let held: Vec<usize> = (0..CURRENCIES.len()).filter(|&i| counts[i] > 0 && allows(CURRENCIES[i])).collect();
+        let step = held.iter().fold(0, |g, &i| gcd(g, CURRENCIES_AS_FARTHINGS[i]));
+        // With nothing held, only nothing can be made.
+        let step = if step == 0 { bound + 1 } else { step };
+        let per_coin = if most { -COIN_COST } else { COIN_COST };
+        let mut costs = vec![UNREACHABLE; bound / step + 1];
+        costs[0] = 0;
+        let mut taken = Vec::with_capacity(held.len());
+        for i in held {
+            let mut next = vec![UNREACHABLE; costs.len()];
+            let mut take = vec![0; costs.len()];
+            let worth = CURRENCIES_AS_FARTHINGS[i] / step;
+            // Using any more than this many runs the denomination down below the threshold.
+            let spare = counts[i].checked_sub(threshold).unwrap_or(counts[i]);
+            knapsack_pass(&costs, &mut next, &mut take, worth, (0, spare), per_coin, 0);
+            if spare < counts[i] {
+                knapsack_pass(&costs, &mut next, &mut take, worth, (spare + 1, counts[i]), per_coin, 1);
+            }
+            costs = next;
+            taken.push((i, take));
+        }
+        CoinTable { step, per_coin, costs, taken }
+    }
+
+    /// How many coins it takes to make `t` farthings, or [`None`] if it can't be made.
+    pub(crate) fn count(&self, t: usize) -> Option<usize> {
+        if !t.is_multiple_of(self.step) {
+            return None;
+        }
+        let cost = *self.costs.get(t / self.step)?;
+        (cost != UNREACHABLE).then(|| (cost.div_euclid(COIN_COST) * self.per_coin.signum()) as usize)
+    }
+
+    /// The coins making `t` farthings. Panics if it can't be made.
+    pub(crate) fn coins(&self, t: usize) -> Wallet {
+        assert!(self.count(t).is_some(), "{} farthings can't be made", t);
+        let mut coins = [0; 17];
+        let mut j = t / self.step;
+        for (i, take) in self.taken.iter().rev() {
+            coins[*i] = take[j] as usize;
+            j -= coins[*i] * (CURRENCIES_AS_FARTHINGS[*i] / self.step);
+        }
+        Wallet::from_counts(coins)
+    }
+}
+
+/// One pass of a bounded knapsack: for every `j`, the cheapest of `prev[j - k * worth]` plus
+/// `per_coin` for each of the `k` coins and `extra` on top, for `k` from `ks.0` to `ks.1`, if that
+/// beats what's in `next[j]` already. The `k` used goes in `take[j]`, and ties go to the smallest `k`.
+///
+/// Rather than trying every `k`, this keeps a queue of the candidates for each residue modulo
+/// `worth`, cheapest at the front, so each entry goes on and comes off the queue at most once.
+fn knapsack_pass(
+    prev: &[i64],
+    next: &mut [i64],
+    take: &mut [u32],
+    worth: usize,
+    ks: (usize, usize),
+    per_coin: i64,
+    extra: i64,
+) {
+    let (fewest, most) = ks;
+    let mut queue = alloc::collections::VecDeque::new();
+    for residue in 0..worth.min(prev.len()) {
+        queue.clear();
+        // Positions along this residue: value j is residue + n * worth.
+        let key = |n: usize| prev[residue + n * worth] - per_coin * n as i64;
+        for n in 0..=(prev.len() - 1 - residue) / worth {
+            if n >= fewest && prev[residue + (n - fewest) * worth] != UNREACHABLE {
+                let candidate = n - fewest;
+                while queue.back().is_some_and(|&b| key(b) >= key(candidate)) {
+                    queue.pop_back();
+                }
+                queue.push_back(candidate);
+            }
+            while queue.front().is_some_and(|&f| n - f > most) {
+                queue.pop_front();
+            }
+            let Some(&from) = queue.front() else { continue };
+            let j = residue + n * worth;
+            let cost = key(from) + per_coin * n as i64 + extra;
+            if next[j] == UNREACHABLE || cost < next[j] {
+                next[j] = cost;
+                take[j] = (n - from) as u32;
+            }
+        }
+    }
+}
+
+/// How [`Wallet::pay`] picks which coins to hand over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PayStrategy {
+    /// Overpay as little as possible, and then hand over as few coins and notes as possible.
+    #[default]
+    FewestCoins,
+    /// Overpay as little as possible, and then get rid of as many coins as possible, so the
+    /// small change gets spent.
+    SmallChangeFirst,
+}
+
+/// The coins handed over by [`Wallet::pay`], and the change due back for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Payment {
+    pub tendered: Wallet,
+    pub change_due: Price,
+}
+
+impl Wallet {
+    /// Pick coins from the wallet to cover `price` and take them out, returning what was handed
+    /// over and how much change is due. If the wallet doesn't hold enough, or the payment would be
+    /// over [`DEFAULT_MAX_TARGET`], [`None`] is returned and the wallet is left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ PayStrategy, Price, Wallet, price };
+    ///
+    /// let mut purse = Wallet { half_crowns: 1, threepence: 2, pennies: 6, ..Default::default() };
+    /// let payment = purse.pay(price!(-/6), PayStrategy::FewestCoins).unwrap();
+    /// assert_eq!(payment.tendered, Wallet { threepence: 2, ..Default::default() });
+    /// let payment = purse.pay(price!(-/6), PayStrategy::SmallChangeFirst).unwrap();
+    /// assert_eq!(payment.tendered, Wallet { pennies: 6, ..Default::default() });
+    /// let payment = purse.pay(price!(1/-), PayStrategy::FewestCoins).unwrap();
+    /// assert_eq!(payment.change_due, price!(1/6));
+    /// assert_eq!(purse, Wallet::default());
+    /// ```
+    pub fn pay(&mut self, price: Price, strategy: PayStrategy) -> Option<Payment> {
+        let target = price.to_farthings();
+        let total = self.to_farthings();
+        if total < target || target > DEFAULT_MAX_TARGET {
+            return None;
+        }
+        // No sensible payment goes over by as much as the biggest coin held, since that coin
+        // could just be kept back.
+        let counts = self.to_counts();
+        let held = CURRENCIES_AS_FARTHINGS.iter().zip(counts).filter(|&(_, n)| n > 0);
+        let biggest = held.map(|(&worth, _)| worth).max().unwrap_or(0);
+        let bound = total.min(target + biggest.saturating_sub(1)).min(DEFAULT_MAX_TARGET);
+        let table = CoinTable::new(self, bound, strategy == PayStrategy::SmallChangeFirst);
+        let t = (target..=bound).find(|&t| table.count(t).is_some())?;
+        let tendered = table.coins(t);
+        *self = Wallet::from_counts(core::array::from_fn(|i| counts[i] - tendered.to_counts()[i]));
+        Some(Payment { tendered, change_due: Price::from_farthings(tendered.to_farthings() - target) })
+    }
+}
+
+/// Lists what's in the wallet, biggest first, followed by the total. The alternate form (`{:#}`)
+/// puts each denomination on its own line.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::Wallet;
+///
+/// let purse = Wallet { half_crowns: 2, shillings: 1, pennies: 3, ..Default::default() };
+/// assert_eq!(purse.to_string(), "2 half crowns, 1 shilling, 3 pennies (total £0 6s 3d)");
+/// assert_eq!(format!("{:#}", purse), "2 half crowns\n1 shilling\n3 pennies\ntotal £0 6s 3d");
+/// assert_eq!(Wallet::default().to_string(), "nothing (total £0 0s 0d)");
+/// ```
+impl Display for Wallet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let separator = if f.alternate() { "\n" } else { ", " };
+        if self.is_empty() && !f.alternate() {
+            write!(f, "nothing")?;
+        }
+        let held = CURRENCIES.iter().rev().map(|&c| (c, self[c])).filter(|&(_, count)| count > 0);
+        for (i, (currency, count)) in held.enumerate() {
+            if i > 0 {
+                write!(f, "{}", separator)?;
+            }
+            write!(f, "{} {}", count, if count == 1 { currency.name() } else { currency.plural() })?;
+        }
+        let total = Price::from_farthings(self.to_farthings());
+        match (f.alternate(), self.is_empty()) {
+            (true, true) => write!(f, "total {}", total),
+            (true, false) => write!(f, "\ntotal {}", total),
+            (false, _) => write!(f, " (total {})", total),
+        }
+    }
+}
+
+/// Empty one wallet into another, e.g. a collection tin into the till.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::Wallet;
+///
+/// let mut till = Wallet { pennies: 10, florins: 2, ..Default::default() };
+/// till += Wallet { pennies: 5, sixpence: 1, ..Default::default() };
+/// assert_eq!(till, Wallet { pennies: 15, sixpence: 1, florins: 2, ..Default::default() });
+/// ```
+impl Add for Wallet {
+    type Output = Wallet;
+
+    fn add(self, rhs: Wallet) -> Wallet {
+        let (left, right) = (self.to_counts(), rhs.to_counts());
+        Wallet::from_counts(core::array::from_fn(|i| left[i] + right[i]))
+    }
+}
+
+impl AddAssign for Wallet {
+    fn add_assign(&mut self, rhs: Wallet) {
+        *self = *self + rhs;
+    }
+}
+
+impl From<Price> for Wallet {
+    fn from(value: Price) -> Self {
+        Self::change_for(value, ChangePolicy::default())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Price {
+    pounds: usize,
+    shillings: usize, 
+    farthings: usize,
+}
+
+impl Price {
+    /// Create a new price. Amounts too big for their unit are carried over, so
+    /// `Price::new(0, 25, 0)` is the same as `Price::new(1, 5, 0)`.
+    pub const fn new(pounds: usize, shillings: usize, halfpence: usize) -> Self {
+        Self::with_farthings(pounds, shillings, halfpence_to_farthings(halfpence))
+    }
+
+    /// Like [`Price::new`], but with the pence given in farthings, e.g.
+    /// `Price::with_farthings(0, 1, 47)` is 1s 11¾d.
+    pub const fn with_farthings(pounds: usize, shillings: usize, farthings: usize) -> Self {
+        Self::from_farthings(pounds_to_farthings(pounds) + shillings_to_farthings(shillings) + farthings)
+    }
+
+    /// Like [`Price::with_farthings`], but [`None`] if the price is too big to hold rather than
+    /// overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// assert_eq!(Price::try_with_farthings(0, 25, 2), Some(Price::new(1, 5, 1)));
+    /// assert_eq!(Price::try_with_farthings(usize::MAX, 0, 0), None);
+    /// ```
+    pub fn try_with_farthings(pounds: usize, shillings: usize, farthings: usize) -> Option<Self> {
+        let farthings = pounds
+            .checked_mul(FARTHINGS_PER_POUND)?
+            .checked_add(shillings.checked_mul(FARTHINGS_PER_SHILLING)?)?
+            .checked_add(farthings)?;
+        Some(Self::from_farthings(farthings))
+    }
+
+    /// Convert a halfpence value to a more readable price.
+    pub const fn from_halfpence(halfpence: usize) -> Self {
+        Self::from_farthings(halfpence_to_farthings(halfpence))
+    }
+
+    /// Convert a farthings value to a more readable price.
+    pub const fn from_farthings(farthings: usize) -> Self {
+        let temp = farthings / FARTHINGS_PER_SHILLING;
+        let farthings = farthings % FARTHINGS_PER_SHILLING;
+        let shillings = temp % SHILLINGS_PER_POUND;
+        Price { pounds: temp / SHILLINGS_PER_POUND, shillings, farthings }
+    }
+
+    /// Convert a pence value to a more readable price. Internally calls [`Price::from_halfpence`].
+    pub const fn from_pence(pence: usize) -> Self {
+        Self::from_halfpence(pence_to_halfpence(pence))
+    }
+
+    /// Convert price to halfpence value. An odd farthing is rounded down, so use
+    /// [`Price::to_farthings`] for the exact value.
+    pub const fn to_halfpence(&self) -> usize {
+        self.to_farthings() / FARTHINGS_PER_HALFPENNY
+    }
+
+    /// Convert price to farthings value.
+    pub const fn to_farthings(&self) -> usize {
+        pounds_to_farthings(self.pounds) + shillings_to_farthings(self.shillings) + self.farthings
+    }
+
+    /// Add a price to this one.
+    pub fn add(&self, rhs: Price) -> Self {
+        let mut temp = self.farthings + rhs.farthings;
+        let farthings = temp % FARTHINGS_PER_SHILLING;
+        temp = temp / FARTHINGS_PER_SHILLING + self.shillings + rhs.shillings;
+        let shillings = temp % SHILLINGS_PER_POUND;
+        Price { pounds: temp / SHILLINGS_PER_POUND + self.pounds + rhs.pounds, shillings, farthings }
+    }
+
+    /// Subtract a price from this one, or [`None`] if the result would be negative. Handy for
+    /// working out change: `paid.checked_sub(cost)`.
+    pub fn checked_sub(&self, rhs: Price) -> Option<Self> {
+        self.to_farthings().checked_sub(rhs.to_farthings()).map(Self::from_farthings)
+    }
+
+    /// Subtract a price from this one, stopping at zero rather than going negative.
+    pub fn saturating_sub(&self, rhs: Price) -> Self {
+        Self::from_farthings(self.to_farthings().saturating_sub(rhs.to_farthings()))
+    }
+
+    /// The difference between two prices, whichever is bigger.
+    pub fn abs_diff(&self, other: Price) -> Self {
+        Self::from_farthings(self.to_farthings().abs_diff(other.to_farthings()))
+    }
+
+    /// Split this price in the ratio given by `weights`, to the nearest farthing. The parts always
+    /// add up to exactly this price: any farthings left over from rounding down go to the parts
+    /// with the largest remainders (the largest remainder method), earlier parts winning ties.
+    ///
+    /// Panics if the weights add up to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// let parts = price!(1/-/-).allocate(&[2, 3, 5]);
+    /// assert_eq!(parts, vec![price!(4/-), price!(6/-), price!(10/-)]);
+    /// let parts = price!(-/1).allocate(&[1, 1, 1]);
+    /// assert_eq!(parts, vec![Price::from_farthings(2), Price::from_farthings(1), Price::from_farthings(1)]);
+    /// ```
+    pub fn allocate(&self, weights: &[u32]) -> Vec<Self> {
+        let total = self.to_farthings() as u128;
+        let weight_sum: u128 = weights.iter().map(|&w| w as u128).sum();
+        assert!(weight_sum > 0, "can't allocate with weights adding up to zero");
+        let mut shares: Vec<u128> = weights.iter().map(|&w| total * w as u128 / weight_sum).collect();
+        let mut by_remainder: Vec<usize> = (0..weights.len()).collect();
+        // Stable sort, so ties keep their original order.
+        by_remainder.sort_by_key(|&i| core::cmp::Reverse(total * weights[i] as u128 % weight_sum));
+        let leftover = total - shares.iter().sum::<u128>();
+        for &i in by_remainder.iter().take(leftover as usize) {
+            shares[i] += 1;
         }
+        shares.into_iter().map(|s| Self::from_farthings(s as usize)).collect()
+    }
+
+    /// Divide the price into `n` equal shares, to the farthing, giving one share and whatever's
+    /// left over.
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// assert_eq!(price!(7/6).divide(3), (price!(2/6), price!(0)));
+    /// assert_eq!(price!(1/1).divide(3), (Price::with_farthings(0, 0, 17), Price::from_farthings(1)));
+    /// ```
+    pub fn divide(&self, n: usize) -> (Price, Price) {
+        assert!(n > 0, "can't divide a price between nobody");
+        let farthings = self.to_farthings();
+        (Self::from_farthings(farthings / n), Self::from_farthings(farthings % n))
+    }
+
+    /// Multiply the price by a whole number, or [`None`] if the result is too big. `price * n`
+    /// does the same but panics instead.
+    pub fn checked_mul(&self, n: usize) -> Option<Self> {
+        self.to_farthings().checked_mul(n).map(Self::from_farthings)
+    }
+
+    /// Multiply the price by `numerator / denominator`, rounding as `rounding` says, e.g. time and
+    /// a half is `wage.mul_frac(3, 2, ..)` and one of a dozen is `price.mul_frac(1, 12, ..)`.
+    ///
+    /// Panics if the denominator is zero or the result is too big.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Price, price };
+    /// use coin_changing::rounding::{ RoundingMode, RoundingPolicy };
+    ///
+    /// assert_eq!(price!(1/3).mul_frac(3, 2, RoundingPolicy::default()), Price::with_farthings(0, 1, 42));
+    /// let to_the_penny = RoundingPolicy::new(RoundingMode::Up, Currency::Penny);
+    /// assert_eq!(price!(1/3).mul_frac(3, 2, to_the_penny), price!(1/11));
+    /// assert_eq!(price!(10/-).mul_frac(1, 12, to_the_penny), price!(-/10));
+    /// ```
+    pub fn mul_frac(&self, numerator: usize, denominator: usize, rounding: rounding::RoundingPolicy) -> Self {
+        self.mul_frac_with_report(numerator, denominator, rounding).0
+    }
+
+    /// Like [`Price::mul_frac`], but also reporting what the rounding did.
+    pub fn mul_frac_with_report(
+        &self,
+        numerator: usize,
+        denominator: usize,
+        rounding: rounding::RoundingPolicy,
+    ) -> (Self, rounding::RoundingReport) {
+        let exact = self.to_farthings() as u128 * numerator as u128;
+        let (farthings, report) = rounding.round_ratio_with_report(exact, denominator as u128);
+        (Self::from_farthings(usize::try_from(farthings).expect("price too large")), report)
+    }
+
+    /// Round the price to a whole number of `currency`, e.g. to the nearest sixpence or up to the
+    /// next shilling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, price };
+    /// use coin_changing::rounding::RoundingMode;
+    ///
+    /// assert_eq!(price!(4/2).round_to(Currency::Threepence, RoundingMode::Nearest), price!(4/3));
+    /// assert_eq!(price!(4/2).round_to(Currency::Sixpence, RoundingMode::Nearest), price!(4/-));
+    /// assert_eq!(price!(4/2).round_to(Currency::Shilling, RoundingMode::Up), price!(5/-));
+    /// assert_eq!(price!(1/3/9).round_to(Currency::OnePound, RoundingMode::Down), price!(1/-/-));
+    /// ```
+    pub fn round_to(&self, currency: Currency, mode: rounding::RoundingMode) -> Self {
+        let farthings = rounding::RoundingPolicy::new(mode, currency).round_ratio(self.to_farthings() as u128, 1);
+        Self::from_farthings(usize::try_from(farthings).expect("price too large"))
     }
 
-    /// Get the halfpence value of the wallet.
-    pub fn to_halfpence(&self) -> usize {
-        self.halfpence
-        + self.pennies * 2
-        + self.threepence * 6
-        + self.sixpence * 12
-        + self.shillings * 24
-        + self.florins * 48
-        + self.half_crowns * 60
-        + self.crowns * 120
-        + self.one_pounds * 480
-        + self.five_pounds * 2400
-        + self.ten_pounds * 4800
-    }
+    /// Like [`Price::round_to`], but also reporting what the rounding did.
+    pub fn round_to_with_report(
+        &self,
+        currency: Currency,
+        mode: rounding::RoundingMode,
+    ) -> (Self, rounding::RoundingReport) {
+        let rounded = self.round_to(currency, mode);
+        (rounded, rounding::RoundingReport::new(mode.rule(), *self, rounded))
+    }
+
+    /// Split the price into `n` shares as evenly as possible, adding up to exactly this price.
+    /// The farthings left over by [`Price::divide`] go one each to the first shares.
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// let shares = price!(10/1).split_evenly(3);
+    /// let (more, less) = (Price::with_farthings(0, 3, 18), Price::with_farthings(0, 3, 17));
+    /// assert_eq!(shares, vec![more, less, less]);
+    /// assert_eq!(shares.into_iter().sum::<Price>(), price!(10/1));
+    /// ```
+    pub fn split_evenly(&self, n: usize) -> Vec<Self> {
+        let (share, left_over) = self.divide(n);
+        let extra = left_over.to_farthings();
+        (0..n).map(|i| if i < extra { share + Self::from_farthings(1) } else { share }).collect()
+    }
+
+    /// The price of `guineas` guineas, at 21s each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// assert_eq!(Price::from_guineas(1), price!(1/1/-));
+    /// assert_eq!(Price::from_guineas(5), price!(5/5/-));
+    /// ```
+    pub const fn from_guineas(guineas: usize) -> Self {
+        Self::from_farthings(shillings_to_farthings(guineas * SHILLINGS_PER_GUINEA))
+    }
+
+    /// Split the price into whole guineas and whatever's left over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// assert_eq!(price!(5/5/-).to_guineas(), (5, price!(0)));
+    /// assert_eq!(price!(2/4/6).to_guineas(), (2, price!(2/6)));
+    /// ```
+    pub fn to_guineas(&self) -> (usize, Price) {
+        let per_guinea = shillings_to_farthings(SHILLINGS_PER_GUINEA);
+        let farthings = self.to_farthings();
+        (farthings / per_guinea, Self::from_farthings(farthings % per_guinea))
+    }
+
+    /// Display the price in guineas if it's a whole number of them, like "5 gns", and as usual
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// assert_eq!(Price::from_guineas(5).in_guineas().to_string(), "5 gns");
+    /// assert_eq!(price!(1/1/-).in_guineas().to_string(), "1 gn");
+    /// assert_eq!(price!(1/-/-).in_guineas().to_string(), "£1 0s 0d");
+    /// ```
+    pub fn in_guineas(&self) -> InGuineas {
+        InGuineas(*self)
+    }
+
+    /// Compare this price against another, reporting the difference and which way it goes. Think
+    /// of `self` as what was paid and `other` as what was asked for.
+    pub fn delta(&self, other: Price) -> PriceDelta {
+        let (paid, asked) = (self.to_farthings(), other.to_farthings());
+        if paid < asked {
+            PriceDelta { direction: Direction::Owed, amount: Self::from_farthings(asked - paid) }
+        } else {
+            PriceDelta { direction: Direction::Due, amount: Self::from_farthings(paid - asked) }
+        }
+    }
+}
+
+/// A [`Price`] displayed in guineas where possible, as returned by [`Price::in_guineas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InGuineas(pub Price);
+
+impl Display for InGuineas {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0.to_guineas() {
+            (1, rest) if rest == Price::default() => write!(f, "1 gn"),
+            (0, _) => write!(f, "{}", self.0),
+            (guineas, rest) if rest == Price::default() => write!(f, "{} gns", guineas),
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// Which way a [`PriceDelta`] goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Not enough was paid, so the payer still owes the amount.
+    Owed,
+    /// Too much (or exactly enough) was paid, so the amount is due back to the payer.
+    Due,
+}
+
+/// The difference between two prices, along with its direction, as returned by
+/// [`Price::delta`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceDelta {
+    pub direction: Direction,
+    pub amount: Price,
 }
 
-impl Default for Wallet {
-    fn default() -> Self {
-        Wallet {
-            halfpence: 0,
-            pennies: 0,
-            threepence: 0,
-            sixpence: 0,
-            shillings: 0,
-            florins: 0,
-            half_crowns: 0,
-            crowns: 0,
-            one_pounds: 0,
-            five_pounds: 0,
-            ten_pounds: 0,
-        }
+impl PriceDelta {
+    /// Whether the two prices were equal, i.e. nothing is owed or due.
+    pub fn is_settled(&self) -> bool {
+        self.amount.to_farthings() == 0
     }
 }
 
-impl From<Price> for Wallet {
-    fn from(value: Price) -> Self {
-        let mut to_return = Self::default();
-        let currencies: Vec<Currency> = coin_change(&CURRENCIES_AS_HALFPENCE, value.to_halfpence())
-            .iter()
-            .map(|&c| Currency::from_halfpence(c).unwrap())
-            .collect();
-        for currency in currencies {
-            to_return.add_currency(currency);
+impl Display for PriceDelta {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.direction {
+            Direction::Owed => write!(f, "{} owed", self.amount),
+            Direction::Due => write!(f, "{} due", self.amount),
         }
-        to_return
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Price {
-    pounds: usize,
-    shillings: usize, 
-    halfpence: usize,
+/// Adding prices carries farthings into shillings and shillings into pounds, same as
+/// [`Price::add`].
+impl Add for Price {
+    type Output = Price;
+
+    fn add(self, rhs: Price) -> Price {
+        Price::add(&self, rhs)
+    }
 }
 
-impl Price {
-    pub fn new(pounds: usize, shillings: usize, halfpence: usize) -> Self {
-        Price { pounds, shillings, halfpence }
+impl AddAssign for Price {
+    fn add_assign(&mut self, rhs: Price) {
+        *self = *self + rhs;
     }
+}
 
-    /// Convert a halfpence value to a more readable price.
-    pub fn from_halfpence(halfpence: usize) -> Self {
-        let halfpence = halfpence % 24;
-        let temp = halfpence / 24;
-        let shillings = temp % 20;
-        Price { pounds: temp / 20, shillings, halfpence }
+/// Subtracting prices borrows from the larger units as needed. Prices can't be negative, so this
+/// panics if `rhs` is bigger than `self`; see [`Price::checked_sub`] for a version which doesn't.
+impl Sub for Price {
+    type Output = Price;
+
+    fn sub(self, rhs: Price) -> Price {
+        self.checked_sub(rhs).expect("attempted to subtract a bigger price from a smaller one")
     }
+}
 
-    /// Convert a pence value to a more readable price. Internally calls [`Price::from_halfpence`].
-    pub fn from_pence(pence: usize) -> Self {
-        Self::from_halfpence(pence * 2)
+impl SubAssign for Price {
+    fn sub_assign(&mut self, rhs: Price) {
+        *self = *self - rhs;
     }
+}
 
-    /// Convert price to halfpence value.
-    pub fn to_halfpence(&self) -> usize {
-        self.pounds * 480 + self.shillings * 24 + self.halfpence
+/// Multiplying a price by a whole number, e.g. for buying several of the same item. The result is
+/// normalised, so `price!(7/6) * 4` is `£1 10s 0d`. Panics if the result is too big to hold, in
+/// release builds as well as debug ones; see [`Price::checked_mul`] for a version which doesn't.
+impl Mul<usize> for Price {
+    type Output = Price;
+
+    fn mul(self, rhs: usize) -> Price {
+        self.checked_mul(rhs).expect("attempted to multiply a price to more than it can hold")
     }
+}
 
-    /// Add a price to this one.
-    pub fn add(&self, rhs: Price) -> Self {
-        let mut temp = self.halfpence + rhs.halfpence;
-        let halfpence = temp % 24;
-        temp = temp / 24 + self.shillings + rhs.shillings;
-        let shillings = temp % 20;
-        Price { pounds: temp / 20 + self.pounds + rhs.pounds, shillings, halfpence }
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl Default for Price {
-    fn default() -> Self {
-        Price { pounds: 0, shillings: 0, halfpence: 0 }
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.to_farthings().cmp(&other.to_farthings())
     }
 }
 
 impl Display for Price {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "£{} {}s {}d", self.pounds, self.shillings, self.halfpence / 2)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (pence, farthings) = farthings_to_pence(self.farthings);
+        let fraction = ["", "¼", "½", "¾"][farthings];
+        write!(f, "£{} {}s {}{}d", self.pounds, self.shillings, pence, fraction)
     }
 }
 
@@ -216,58 +1815,64 @@ impl Display for Price {
 /// assert_eq!(price!(1/4/-).to_string(), "£1 4s 0d".to_string());
 /// assert_eq!(price!(-/2).to_string(), "£0 0s 2d".to_string());
 /// ```
+///
+/// It can be used in `const` items, for example to make a price list:
+///
+/// ```
+/// use coin_changing::{ Currency, Price, price };
+///
+/// const PRICES: [Price; 3] = [price!(-/6), price!(1/4), Currency::HalfCrown.value()];
+/// const TOTAL: usize = PRICES[0].to_farthings() + PRICES[1].to_farthings() + PRICES[2].to_farthings();
+/// assert_eq!(Price::from_farthings(TOTAL), price!(4/4));
+/// ```
+///
+/// As well as numbers, each amount can be a variable, or any expression in brackets:
+///
+/// ```
+/// use coin_changing::{ Price, price };
+///
+/// let (shillings, pence) = (3, 4);
+/// assert_eq!(price!(shillings/pence), price!(3/4));
+/// assert_eq!(price!(1/(shillings * 2)/-), price!(1/6/-));
+/// assert_eq!(price!(-/(pence + 2)), price!(-/6));
+/// ```
 #[macro_export]
 macro_rules! price {
     ( 0 ) => {
-        Price::new(0, 0, 0)
+        $crate::Price::new(0, 0, 0)
     };
     ( -/-/- ) => {
-        Price::new(0, 0, 0)
-    };
-    ( -/$pence:literal ) => {
-        Price::new(0, 0, $pence * 2)
+        $crate::Price::new(0, 0, 0)
     };
-    ( $shillings:literal/- ) => {
-        Price::new(0, $shillings, 0)
+    ( -/$pence:tt ) => {
+        $crate::Price::new(0, 0, $crate::pence_to_halfpence($pence))
     };
-    ( -/-/$pence:literal ) => {
-        Price::new(0, 0, $pence * 2)
+    ( $shillings:tt/- ) => {
+        $crate::Price::new(0, $shillings, 0)
     };
-    ( -/$shillings:literal/- ) => {
-        Price::new(0, $shilligns, 0)
+    ( -/-/$pence:tt ) => {
+        $crate::Price::new(0, 0, $crate::pence_to_halfpence($pence))
     };
-    ( $pounds:literal/-/- ) => {
-        Price::new($pounds, $shilligns, 0)
+    ( -/$shillings:tt/- ) => {
+        $crate::Price::new(0, $shillings, 0)
     };
-    ( $pounds:literal/$shillings:literal/- ) => {
-        Price::new($pounds, $shillings, 0)
+    ( $pounds:tt/-/- ) => {
+        $crate::Price::new($pounds, 0, 0)
     };
-    ( $shillings:literal/$pence:literal ) => {
-        Price::new(0, $shillings, $pence * 2)
+    ( $pounds:tt/$shillings:tt/- ) => {
+        $crate::Price::new($pounds, $shillings, 0)
     };
-    ( $pounds:literal/$shillings:literal/- ) => {
-        Price::new($pounds, $shilligns, 0)
+    ( $shillings:tt/$pence:tt ) => {
+        $crate::Price::new(0, $shillings, $crate::pence_to_halfpence($pence))
     };
-    ( $pounds:literal/$shillings:literal/$pence:literal ) => {
-        Price::new($pounds, $shillings, $pence * 2)
+    ( $pounds:tt/$shillings:tt/$pence:tt ) => {
+        $crate::Price::new($pounds, $shillings, $crate::pence_to_halfpence($pence))
     };
 }
 
 impl From<Currency> for Price {
     fn from(value: Currency) -> Price {
-        match value {
-            Currency::Halfpenny => Price { pounds: 0, shillings: 0, halfpence: 1 },
-            Currency::Penny => Price { pounds: 0, shillings: 0, halfpence: 2 },
-            Currency::Threepence => Price { pounds: 0, shillings: 0, halfpence: 3 },
-            Currency::Sixpence => Price { pounds: 0, shillings: 0, halfpence: 6 },
-            Currency::Shilling => Price { pounds: 0, shillings: 1, halfpence: 0 },
-            Currency::Florin => Price { pounds: 0, shillings: 2, halfpence: 0 },
-            Currency::HalfCrown => Price { pounds: 0, shillings: 2, halfpence: 6 },
-            Currency::Crown => Price { pounds: 0, shillings: 5, halfpence: 0 },
-            Currency::OnePound => Price { pounds: 1, shillings: 0, halfpence: 0 },
-            Currency::FivePound => Price { pounds: 5, shillings: 0, halfpence: 0 },
-            Currency::TenPound => Price { pounds: 10, shillings: 0, halfpence: 0 },
-        }
+        value.value()
     }
 }
 
@@ -281,11 +1886,68 @@ impl Sum for Price {
 
 impl From<Wallet> for Price {
     fn from(value: Wallet) -> Self {
-        Self::from_halfpence(value.to_halfpence())
+        Self::from_farthings(value.to_farthings())
+    }
+}
+
+/// Why coins couldn't be taken out of a wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletError {
+    /// The wallet was `short` coins of `currency`.
+    NotEnough { currency: Currency, short: usize },
+}
+
+impl Display for WalletError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WalletError::NotEnough { currency, short } => write!(f, "{} short of {}", short, currency),
+        }
+    }
+}
+
+impl core::error::Error for WalletError {}
+
+/// Why change couldn't be made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeError {
+    /// No coins were given to make change with.
+    NoCoins,
+    /// One of the coins was worth nothing.
+    ZeroCoin,
+    /// The coins can't add up to the target exactly.
+    Unreachable { target: usize },
+    /// The target is over the limit on the size of the table, either [`DEFAULT_MAX_TARGET`] or the
+    /// one a [`ChangeMaker`] was set up with. Greedy change doesn't have a limit, so
+    /// [`Algorithm::Greedy`] might do instead.
+    TooLarge { target: usize, max: usize },
+    /// A coin or the target doesn't fit in a `usize`, so there'd be no way to index a table by it.
+    Overflow,
+    /// Change was to be counted up as a [`ChangeBreakdown`](breakdown::ChangeBreakdown), but a
+    /// coin isn't worth as many farthings as any real denomination.
+    NotADenomination { coin: usize },
+}
+
+impl Display for ChangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ChangeError::NoCoins => write!(f, "no coins to make change with"),
+            ChangeError::ZeroCoin => write!(f, "a coin was worth nothing"),
+            ChangeError::Unreachable { target } => write!(f, "{} can't be made from the coins given", target),
+            ChangeError::TooLarge { target, max } => {
+                write!(f, "{} is over the maximum target of {} (greedy change has no maximum)", target, max)
+            }
+            ChangeError::Overflow => write!(f, "a coin or the target is too big to make change with"),
+            ChangeError::NotADenomination { coin } => write!(f, "no coin or note is worth {} farthings", coin),
+        }
     }
 }
 
+impl core::error::Error for ChangeError {}
+
 /// Calculates change for a given target.
+///
+/// Panics if the target can't be made from the coins; see [`try_coin_change`] for a version
+/// which doesn't.
 /// 
 /// # Example
 /// 
@@ -295,43 +1957,398 @@ impl From<Wallet> for Price {
 /// assert_eq!(coin_change(&[1, 5, 7], 20), vec![7, 7, 5, 1]);
 /// ```
 pub fn coin_change(coins: &[usize], target: usize) -> Vec<usize> {
-    // Taken from my lecture notes. Eventually I'll clean it up :P
-    if target == 0 {
-        return vec![];
-    }
-    let mut v = target;
-    let mut k = coins.len();
-    #[allow(non_snake_case)]
-    let mut C: Vec<usize> = (0..=v).collect();
-    #[allow(non_snake_case)]
-    let mut P = vec![0; v + 1];
-    #[allow(non_snake_case)]
-    let mut S = vec![0; k];
-    let mut to_return: Vec<usize> = Vec::new();
-    
-    C[1] = 1;
-    for w in 2..=v {
-        for i in 0..k {
-            if (coins[i] <= w) && (C[w - coins[i]] + 1 < C[w]) {
-                C[w] = C[w - coins[i]] + 1;
-                P[w] = i;
+    unbounded_coin_change(coins, target).expect("couldn't make change")
+}
+
+/// The biggest target [`try_coin_change`] will take on, which is £10,000 in farthings. The table
+/// it builds takes a couple of dozen bytes per farthing, so this keeps it to about 230 megabytes.
+///
+/// The wallet methods which can only use the coins held, like [`Wallet::make_change`],
+/// [`Wallet::change_preserving`], [`Wallet::exact_payment`] and [`Wallet::pay`], have the same
+/// limit but a different table. It takes 16 bytes, plus 4 for each denomination held, for every
+/// step up to the target, where a step is the largest amount every coin held is a multiple of. A
+/// wallet with farthings and every other denomination in it needs 84 bytes a farthing, which is
+/// about 800 megabytes at the limit, while one holding only ten pound notes needs a few kilobytes.
+pub const DEFAULT_MAX_TARGET: usize = 10_000 * FARTHINGS_PER_POUND;
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Like [`try_coin_change`], but making change for targets over [`DEFAULT_MAX_TARGET`] too, as
+/// long as the coins allow it. That's true of any set of £sd denominations, since they all go
+/// into £10 (or whatever the largest is) a whole number of times.
+///
+/// Some change with the fewest coins has fewer than `largest / gcd(coin, largest)` of each other
+/// coin, since that many add up to a whole number of the largest coin, which would take fewer
+/// coins. So everything but the largest coins adds up to at most `rest`, and only the last `rest`
+/// or so of the target needs a table.
+pub(crate) fn unbounded_coin_change(coins: &[usize], target: usize) -> Result<Vec<usize>, ChangeError> {
+    check_coins(coins)?;
+    let largest = *coins.iter().max().unwrap();
+    let rest = coins.iter().try_fold(0usize, |sum, &c| sum.checked_add((largest / gcd(c, largest) - 1).checked_mul(c)?));
+    let Some(rest) = rest.filter(|&r| target > DEFAULT_MAX_TARGET && r.saturating_add(largest) <= DEFAULT_MAX_TARGET)
+    else {
+        return try_coin_change(coins, target);
+    };
+    let largest_count = (target - rest) / largest;
+    let mut change = try_coin_change(coins, target - largest_count * largest).map_err(|e| match e {
+        ChangeError::Unreachable { .. } => ChangeError::Unreachable { target },
+        e => e,
+    })?;
+    // Put the largest coins where they'd have gone, as coins come out in the reverse of the order given.
+    let index = |coin: usize| coins.iter().position(|&c| c == coin).unwrap();
+    let at = change.iter().take_while(|&&c| index(c) > index(largest)).count();
+    change.splice(at..at, core::iter::repeat_n(largest, largest_count));
+    Ok(change)
+}
+
+/// Make sure there are coins to make change with, and none of them are worth nothing.
+fn check_coins(coins: &[usize]) -> Result<(), ChangeError> {
+    if coins.is_empty() {
+        return Err(ChangeError::NoCoins);
+    }
+    if coins.contains(&0) {
+        return Err(ChangeError::ZeroCoin);
+    }
+    Ok(())
+}
+
+/// Calculates change for a given target using as few coins as possible, or says why it can't.
+/// Coins are returned largest first (or rather, in the reverse of the order they're given in).
+///
+/// Targets over [`DEFAULT_MAX_TARGET`] are refused with [`ChangeError::TooLarge`] rather than
+/// risking running out of memory; use [`try_coin_change_up_to`] to pick a different limit, or
+/// [`Algorithm::Greedy`] which doesn't need a table at all.
+///
+/// # Example
+///
+/// ```
+/// use coin_changing::{ try_coin_change, ChangeError, DEFAULT_MAX_TARGET };
+///
+/// assert_eq!(try_coin_change(&[1, 5, 7], 20), Ok(vec![7, 7, 5, 1]));
+/// assert_eq!(try_coin_change(&[5, 7], 3), Err(ChangeError::Unreachable { target: 3 }));
+/// assert_eq!(try_coin_change(&[], 3), Err(ChangeError::NoCoins));
+/// let target = usize::MAX;
+/// assert_eq!(try_coin_change(&[1], target), Err(ChangeError::TooLarge { target, max: DEFAULT_MAX_TARGET }));
+/// ```
+pub fn try_coin_change(coins: &[usize], target: usize) -> Result<Vec<usize>, ChangeError> {
+    try_coin_change_up_to(coins, target, DEFAULT_MAX_TARGET)
+}
+
+/// Like [`try_coin_change`], but refusing targets over `max_target` instead of
+/// [`DEFAULT_MAX_TARGET`].
+///
+/// # Example
+///
+/// ```
+/// use coin_changing::{ try_coin_change_up_to, ChangeError };
+///
+/// assert_eq!(try_coin_change_up_to(&[1, 5, 7], 20, 100), Ok(vec![7, 7, 5, 1]));
+/// assert_eq!(try_coin_change_up_to(&[1, 5, 7], 200, 100), Err(ChangeError::TooLarge { target: 200, max: 100 }));
+/// ```
+pub fn try_coin_change_up_to(coins: &[usize], target: usize, max_target: usize) -> Result<Vec<usize>, ChangeError> {
+    check_coins(coins)?;
+    if target > max_target {
+        return Err(ChangeError::TooLarge { target, max: max_target });
+    }
+    ChangeMaker::new(coins, target)?.change(target)
+}
+
+/// Like [`try_coin_change`], but for any integer type, e.g. `u64` amounts read from a file.
+/// Everything is converted to `usize` to do the work and back again afterwards, and if anything
+/// doesn't fit then [`ChangeError::Overflow`] is returned instead of the value being truncated.
+///
+/// # Example
+///
+/// ```
+/// use coin_changing::{ try_coin_change_as, ChangeError };
+///
+/// assert_eq!(try_coin_change_as(&[1u64, 5, 7], 20), Ok(vec![7, 7, 5, 1]));
+/// assert_eq!(try_coin_change_as(&[1u8, 4], 8), Ok(vec![4, 4]));
+/// assert_eq!(try_coin_change_as(&[1i32, -2], 3), Err(ChangeError::Overflow));
+/// ```
+pub fn try_coin_change_as<T>(coins: &[T], target: T) -> Result<Vec<T>, ChangeError>
+where
+    T: Copy + TryInto<usize> + TryFrom<usize>,
+{
+    let converted: Option<Vec<usize>> = coins.iter().map(|&c| c.try_into().ok()).collect();
+    let converted = converted.ok_or(ChangeError::Overflow)?;
+    let target = target.try_into().map_err(|_| ChangeError::Overflow)?;
+    let change = try_coin_change(&converted, target)?;
+    // Every coin in the change came from `coins`, so converting back can't fail.
+    change.into_iter().map(|c| T::try_from(c).map_err(|_| ChangeError::Overflow)).collect()
+}
+
+/// Makes change from one set of coins over and over, working out the table of fewest coins once
+/// up front rather than on every call like [`try_coin_change`] does. Answers come out the same as
+/// [`try_coin_change`]'s.
+///
+/// # Example
+///
+/// ```
+/// use coin_changing::{ ChangeError, ChangeMaker };
+///
+/// let maker = ChangeMaker::new(&[1, 5, 7], 100).unwrap();
+/// assert_eq!(maker.change(20), Ok(vec![7, 7, 5, 1]));
+/// assert_eq!(maker.change(14), Ok(vec![7, 7]));
+/// assert_eq!(maker.change(101), Err(ChangeError::TooLarge { target: 101, max: 100 }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeMaker {
+    coins: Vec<usize>,
+    /// fewest[w] is the fewest coins adding up to w, and last[w] the index of a coin used for it.
+    fewest: Vec<Option<usize>>,
+    last: Vec<usize>,
+}
+
+impl ChangeMaker {
+    /// Work out change for every target up to and including `max_target`.
+    pub fn new(coins: &[usize], max_target: usize) -> Result<Self, ChangeError> {
+        check_coins(coins)?;
+        let mut fewest: Vec<Option<usize>> = vec![None; max_target + 1];
+        let mut last = vec![0; max_target + 1];
+        fewest[0] = Some(0);
+        for w in 1..=max_target {
+            for (i, &coin) in coins.iter().enumerate() {
+                if coin > w {
+                    continue;
+                }
+                let Some(count) = fewest[w - coin] else { continue };
+                if fewest[w].is_none_or(|best| count + 1 < best) {
+                    fewest[w] = Some(count + 1);
+                    last[w] = i;
+                }
+            }
+        }
+        Ok(ChangeMaker { coins: coins.to_vec(), fewest, last })
+    }
+
+    /// The coins change is made from, in the order they were given.
+    pub fn coins(&self) -> &[usize] {
+        &self.coins
+    }
+
+    /// The biggest target this can make change for.
+    pub fn max_target(&self) -> usize {
+        self.fewest.len() - 1
+    }
+
+    /// The fewest coins adding up to `target`, or [`None`] if it can't be made or is too large.
+    pub fn fewest_coins(&self, target: usize) -> Option<usize> {
+        self.fewest.get(target).copied().flatten()
+    }
+
+    /// Make change for `target`, largest coins first, like [`try_coin_change`].
+    pub fn change(&self, target: usize) -> Result<Vec<usize>, ChangeError> {
+        let max = self.max_target();
+        if target > max {
+            return Err(ChangeError::TooLarge { target, max });
+        }
+        let Some(fewest) = self.fewest[target] else {
+            return Err(ChangeError::Unreachable { target });
+        };
+        let mut counts = vec![0; self.coins.len()];
+        let mut v = target;
+        while v > 0 {
+            counts[self.last[v]] += 1;
+            v -= self.coins[self.last[v]];
+        }
+        let mut to_return = Vec::with_capacity(fewest);
+        for (&coin, &count) in self.coins.iter().zip(&counts).rev() {
+            to_return.extend(core::iter::repeat_n(coin, count));
+        }
+        Ok(to_return)
+    }
+}
+
+/// How change is worked out by [`try_coin_change_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    /// Take the biggest coin which fits, over and over. Fast, but only gives the fewest coins
+    /// for canonical coin systems (see [`is_canonical`]), and can miss a solution entirely
+    /// otherwise.
+    Greedy,
+    /// Work out the fewest coins for every amount up to the target, like [`try_coin_change`].
+    DynamicProgramming,
+    /// Greedy if the coins are canonical, otherwise dynamic programming. Checking costs about as
+    /// much as one dynamic programming run up to the sum of the two biggest coins. Unlike
+    /// [`Algorithm::DynamicProgramming`], targets over [`DEFAULT_MAX_TARGET`] are fine as long as
+    /// only the last part of the target needs a table, which is always true of £sd.
+    #[default]
+    Auto,
+}
+
+/// Whether taking the biggest coin that fits always gives the fewest coins for these coins. The
+/// half crown spoils it for £sd: greedy makes 4s as a half crown, a shilling, and a sixpence instead
+/// of two florins.
+///
+/// Uses the result of Kozen and Zaks that if greedy is ever beaten, it's beaten on some amount
+/// less than the sum of the two biggest coins, so this builds a table that big.
+///
+/// # Example
+///
+/// ```
+/// use coin_changing::is_canonical;
+///
+/// assert!(is_canonical(&[1, 2, 4, 12, 24, 48, 96, 240]));
+/// assert!(!is_canonical(&[1, 2, 4, 12, 24, 48, 96, 120, 240]));
+/// // Any two coins including 1 are canonical.
+/// assert!(is_canonical(&[5, 1]));
+/// ```
+pub fn is_canonical(coins: &[usize]) -> bool {
+    let mut sorted = coins.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    match sorted.as_slice() {
+        [1] => true,
+        [1, .., largest] => {
+            let second = sorted[sorted.len() - 2];
+            let bound = second + largest;
+            let mut fewest = vec![0; bound];
+            for x in 1..bound {
+                fewest[x] = sorted.iter().filter(|&&c| c <= x).map(|&c| fewest[x - c] + 1).min().unwrap();
+                let biggest = sorted.iter().rev().find(|&&c| c <= x).unwrap();
+                // Greedy takes the biggest coin first, and is optimal below x by now.
+                if fewest[x - biggest] + 1 > fewest[x] {
+                    return false;
+                }
             }
+            true
         }
+        _ => false,
+    }
+}
+
+/// Whether the coins are canonical, without checking at all if it'd mean a table bigger than
+/// [`DEFAULT_MAX_TARGET`].
+fn canonical_within_limit(coins: &[usize]) -> bool {
+    let mut sorted = coins.to_vec();
+    sorted.sort_unstable();
+    match sorted.as_slice() {
+        [.., second, largest] if second.saturating_add(*largest) > DEFAULT_MAX_TARGET => false,
+        _ => is_canonical(coins),
+    }
+}
+
+/// Make change by always taking the biggest coin which fits.
+fn greedy_coin_change(coins: &[usize], target: usize) -> Result<Vec<usize>, ChangeError> {
+    let mut by_size: Vec<usize> = (0..coins.len()).collect();
+    by_size.sort_by_key(|&i| core::cmp::Reverse(coins[i]));
+    let mut counts = vec![0; coins.len()];
+    let mut left = target;
+    for i in by_size {
+        counts[i] = left / coins[i];
+        left %= coins[i];
+    }
+    if left > 0 {
+        return Err(ChangeError::Unreachable { target });
+    }
+    let mut to_return = Vec::with_capacity(counts.iter().sum());
+    for (&coin, &count) in coins.iter().zip(&counts).rev() {
+        to_return.extend(core::iter::repeat_n(coin, count));
     }
-    let mut i;
-    while v > 0 {
-        i = P[v];
-        S[i] += 1;
-        v -= coins[i];
+    Ok(to_return)
+}
+
+/// Like [`try_coin_change`], but choosing the algorithm.
+///
+/// # Example
+///
+/// ```
+/// use coin_changing::{ try_coin_change_with, Algorithm };
+///
+/// assert_eq!(try_coin_change_with(&[1, 3, 4], 6, Algorithm::Greedy), Ok(vec![4, 1, 1]));
+/// assert_eq!(try_coin_change_with(&[1, 3, 4], 6, Algorithm::Auto), Ok(vec![3, 3]));
+/// ```
+pub fn try_coin_change_with(coins: &[usize], target: usize, algorithm: Algorithm) -> Result<Vec<usize>, ChangeError> {
+    check_coins(coins)?;
+    match algorithm {
+        Algorithm::Greedy => greedy_coin_change(coins, target),
+        Algorithm::DynamicProgramming => try_coin_change(coins, target),
+        Algorithm::Auto if canonical_within_limit(coins) => greedy_coin_change(coins, target),
+        Algorithm::Auto => unbounded_coin_change(coins, target),
     }
-    while k > 0 {
-        while S[k - 1] > 0 {
-            to_return.push(coins[k - 1]);
-            S[k - 1] -= 1;
+}
+
+/// Every way of making a target with the fewest coins, as returned by [`minimal_changes`]. Each
+/// solution is given in the same order as [`coin_change`] gives its one.
+#[derive(Debug, Clone)]
+pub struct MinimalChanges<'a> {
+    coins: &'a [usize],
+    fewest: Vec<Option<usize>>,
+    /// Indices of the coins chosen so far.
+    path: Vec<usize>,
+    /// For each coin chosen so far (and the start), what's still to be made and the index below
+    /// which to look for the next coin.
+    stack: Vec<(usize, usize)>,
+}
+
+impl Iterator for MinimalChanges<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        loop {
+            let (remaining, upper) = self.stack.last_mut()?;
+            if *remaining == 0 {
+                let solution = self.path.iter().map(|&i| self.coins[i]).collect();
+                self.stack.pop();
+                self.path.pop();
+                return Some(solution);
+            }
+            let fewest = self.fewest[*remaining].unwrap();
+            let mut next = None;
+            while *upper > 0 {
+                *upper -= 1;
+                let coin = self.coins[*upper];
+                if coin <= *remaining && self.fewest[*remaining - coin] == Some(fewest - 1) {
+                    next = Some((*upper, *remaining - coin));
+                    break;
+                }
+            }
+            match next {
+                Some((i, remaining)) => {
+                    self.path.push(i);
+                    self.stack.push((remaining, i + 1));
+                }
+                None => {
+                    self.stack.pop();
+                    self.path.pop();
+                }
+            }
         }
-        k -= 1;
     }
-    to_return
+}
+
+/// Like [`try_coin_change`], but giving every solution with the fewest coins rather than just
+/// one. Solutions are worked out lazily, so only the table of coin counts is kept in memory.
+///
+/// # Example
+///
+/// ```
+/// use coin_changing::minimal_changes;
+///
+/// let all: Vec<Vec<usize>> = minimal_changes(&[1, 2, 3], 4).unwrap().collect();
+/// assert_eq!(all, vec![vec![3, 1], vec![2, 2]]);
+/// ```
+pub fn minimal_changes(coins: &[usize], target: usize) -> Result<MinimalChanges<'_>, ChangeError> {
+    check_coins(coins)?;
+    if target > DEFAULT_MAX_TARGET {
+        return Err(ChangeError::TooLarge { target, max: DEFAULT_MAX_TARGET });
+    }
+    let mut fewest: Vec<Option<usize>> = vec![None; target + 1];
+    fewest[0] = Some(0);
+    for w in 1..=target {
+        fewest[w] = coins.iter().filter(|&&c| c <= w).filter_map(|&c| fewest[w - c]).min().map(|n| n + 1);
+    }
+    if fewest[target].is_none() {
+        return Err(ChangeError::Unreachable { target });
+    }
+    Ok(MinimalChanges { coins, fewest, path: Vec::new(), stack: vec![(target, coins.len())] })
 }
 
 #[cfg(test)]
@@ -344,6 +2361,95 @@ mod tests {
         assert_eq!(prices.iter().copied().sum::<Price>(), expected_sum);
     }
 
+    #[test]
+    fn price_ordering() {
+        assert_eq!(price!(0/25/-), price!(1/5/-));
+        let mut prices = vec![price!(1/-/-), price!(-/11), price!(19/11), price!(1/-)];
+        prices.sort();
+        assert_eq!(prices, vec![price!(-/11), price!(1/-), price!(19/11), price!(1/-/-)]);
+        assert_eq!(prices.iter().min(), Some(&price!(-/11)));
+        assert_eq!(price!(2/6).max(price!(2/5)), price!(2/6));
+    }
+
+    #[test]
+    fn price_operators() {
+        let mut total = price!(3/16/11) + price!(5/15/10);
+        assert_eq!(total, price!(9/12/9));
+        total -= price!(12/10);
+        assert_eq!(total, price!(8/19/11));
+        total += price!(-/1);
+        assert_eq!(total, price!(9/-/-));
+        assert_eq!(price!(7/6) * 4, price!(1/10/-));
+        assert_eq!(price!(1/-/-) - price!(-/1), price!(19/11));
+    }
+
+    #[test]
+    fn price_checked_sub() {
+        assert_eq!(price!(2/-).checked_sub(price!(1/11)), Some(price!(-/1)));
+        assert_eq!(price!(1/11).checked_sub(price!(2/-)), None);
+        assert_eq!(price!(1/11).saturating_sub(price!(2/-)), price!(0));
+        assert_eq!(price!(1/11).abs_diff(price!(2/-)), price!(-/1));
+        assert_eq!(price!(2/-).abs_diff(price!(1/11)), price!(-/1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn price_sub_underflow() {
+        let _ = price!(1/-) - price!(1/1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn price_mul_overflow() {
+        let _ = price!(1/-/-) * usize::MAX;
+    }
+
+    #[test]
+    fn allocate_sums_to_whole() {
+        let parts = price!(7/6).allocate(&[1, 1, 1, 4]);
+        assert_eq!(parts.iter().copied().sum::<Price>(), price!(7/6));
+        assert_eq!(parts, vec![Price::from_farthings(52), Price::from_farthings(51), Price::from_farthings(51), Price::from_farthings(206)]);
+    }
+
+    #[test]
+    fn split_evenly_sums_to_whole() {
+        for n in 1..20 {
+            let shares = price!(7/6).split_evenly(n);
+            assert_eq!(shares.len(), n);
+            assert_eq!(shares.iter().copied().sum::<Price>(), price!(7/6));
+            assert!(shares[0].to_farthings() - shares[n - 1].to_farthings() <= 1);
+        }
+        assert_eq!(price!(0).divide(4), (price!(0), price!(0)));
+    }
+
+    #[test]
+    fn fractional_multiplication() {
+        use rounding::{RoundingMode, RoundingPolicy};
+        let down = RoundingPolicy::new(RoundingMode::Down, Currency::Halfpenny);
+        assert_eq!(price!(7/6).mul_frac(1, 1, down), price!(7/6));
+        assert_eq!(price!(-/1).mul_frac(1, 3, down), price!(0));
+        assert_eq!(price!(-/1).mul_frac(1, 3, RoundingPolicy::default()), Price::from_farthings(1));
+        assert_eq!(price!(2/-).mul_frac(144, 12, down), price!(1/4/-));
+        assert_eq!(Price::from_farthings(usize::MAX).checked_mul(2), None);
+        assert_eq!(price!(7/6).checked_mul(4), Some(price!(1/10/-)));
+        let (third, report) = price!(-/1).mul_frac_with_report(1, 3, down);
+        assert_eq!((third, report.direction, report.amount), (price!(0), rounding::RoundingDirection::Down, Price::from_farthings(1)));
+    }
+
+    #[test]
+    fn rounding_to_coins() {
+        use rounding::{RoundingDirection, RoundingMode};
+        // Halfway between two half crowns goes up.
+        assert_eq!(price!(3/9).round_to(Currency::HalfCrown, RoundingMode::Nearest), price!(5/-));
+        assert_eq!(price!(5/-).round_to(Currency::HalfCrown, RoundingMode::Up), price!(5/-));
+        let three_farthings = Price::from_farthings(3);
+        assert_eq!(three_farthings.round_to(Currency::Halfpenny, RoundingMode::Down), Price::from_farthings(2));
+        assert_eq!(price!(2/1).round_to(Currency::Florin, RoundingMode::Nearest), price!(2/-));
+        let (rounded, report) = price!(19/11).round_to_with_report(Currency::OnePound, RoundingMode::Nearest);
+        assert_eq!(rounded, price!(1/-/-));
+        assert_eq!((report.direction, report.amount), (RoundingDirection::Up, price!(-/1)));
+    }
+
     #[test]
     fn add_and_change() {
         let price1 = price!(3/16/11);
@@ -351,18 +2457,335 @@ mod tests {
         let total = price!(9/12/9);
         assert_eq!(price1.add(price2), total);
         let change = Wallet {
+            farthings: 0,
             halfpence: 0,
             pennies: 0,
             threepence: 1,
+            groats: 0,
             sixpence: 0,
             half_crowns: 1,
+            double_florins: 0,
             crowns: 2,
             shillings: 0,
             florins: 0,
+            half_sovereigns: 0,
+            ten_shillings: 0,
+            sovereigns: 0,
             one_pounds: 4,
             five_pounds: 1,
             ten_pounds: 0,
         };
         assert_eq!(Wallet::from(total), change);
     }
+
+    #[test]
+    fn commemorative_crowns_not_given() {
+        let with_crowns = Wallet { crowns: 1, half_crowns: 1, ..Default::default() };
+        assert_eq!(Wallet::from(price!(7/6)), with_crowns);
+        let without = Wallet { half_crowns: 3, ..Default::default() };
+        assert_eq!(Wallet::change_for(price!(7/6), CrownPolicy::Commemorative), without);
+    }
+
+    #[test]
+    fn halfpenny_withdrawal() {
+        let price = Price::new(0, 1, 5);
+        assert_eq!(price.to_string(), "£0 1s 2½d");
+        let withdrawn = HalfpennyPolicy::for_year(1970);
+        assert_eq!(withdrawn.format(price), "£0 1s 3d");
+        let change = Wallet { shillings: 1, threepence: 1, ..Default::default() };
+        assert_eq!(Wallet::change_for(price, withdrawn), change);
+        assert_eq!(Wallet::change_for(Price::from_halfpence(1), HalfpennyPolicy::for_year(1969)).halfpence, 1);
+    }
+
+    #[test]
+    fn max_payable_respects_counts() {
+        let wallet = Wallet { shillings: 2, pennies: 3, ..Default::default() };
+        assert_eq!(wallet.max_payable_without_change(price!(1/11)).0, price!(1/3));
+        assert_eq!(wallet.max_payable_without_change(price!(5/-)), (price!(2/3), wallet));
+        assert_eq!(wallet.max_payable_without_change(price!(0)), (price!(0), Wallet::default()));
+    }
+
+    #[test]
+    fn exact_payments() {
+        let purse = Wallet { florins: 2, shillings: 1, ..Default::default() };
+        assert!(purse.can_pay_exactly(price!(3/-)));
+        assert!(purse.can_pay_exactly(price!(0)));
+        assert!(!purse.can_pay_exactly(price!(6/-)));
+        let payment = purse.exact_payment(price!(4/-)).unwrap();
+        assert_eq!(payment, Wallet { florins: 2, ..Default::default() });
+        let fortune = Wallet { ten_pounds: 2_000, ..Default::default() };
+        assert!(!fortune.can_pay_exactly(price!(15_000/-/-)));
+        assert_eq!(fortune.max_payable_without_change(price!(15_000/-/-)).0, price!(10_000/-/-));
+    }
+
+    #[test]
+    fn make_change_from_wallet() {
+        // Greedy would start with the half crown and get stuck.
+        let purse = Wallet { half_crowns: 1, florins: 3, ..Default::default() };
+        assert_eq!(purse.make_change(price!(6/-)), Ok(Wallet { florins: 3, ..Default::default() }));
+        assert_eq!(purse.make_change(price!(0)), Ok(Wallet::default()));
+        assert!(purse.make_change(price!(10/-)).is_err());
+        let fortune = Wallet { ten_pounds: 2_000, ..Default::default() };
+        let target = price!(15_000/-/-).to_farthings();
+        assert_eq!(fortune.make_change(price!(15_000/-/-)), Err(ChangeError::TooLarge { target, max: DEFAULT_MAX_TARGET }));
+    }
+
+    #[test]
+    fn coin_table_matches_trying_everything() {
+        let wallet = Wallet { pennies: 3, threepence: 2, sixpence: 1, shillings: 2, ..Default::default() };
+        let bound = wallet.to_farthings();
+        let (fewest, most) = (CoinTable::new(&wallet, bound, false), CoinTable::new(&wallet, bound, true));
+        let mut expected: Vec<Option<(usize, usize)>> = vec![None; bound + 1];
+        // Every combination of the coins held: up to 3 pennies, 2 threepences, a sixpence and 2 shillings.
+        for combination in 0..4 * 3 * 2 * 3 {
+            let counts = [combination % 4, combination / 4 % 3, combination / 12 % 2, combination / 24];
+            let value = counts[0] * 4 + counts[1] * 12 + counts[2] * 24 + counts[3] * 48;
+            let coins = counts.iter().sum();
+            let (low, high) = expected[value].unwrap_or((coins, coins));
+            expected[value] = Some((low.min(coins), high.max(coins)));
+        }
+        for (t, range) in expected.into_iter().enumerate() {
+            assert_eq!(fewest.count(t).zip(most.count(t)), range, "{} farthings", t);
+            if let Some((low, high)) = range {
+                assert_eq!((fewest.coins(t).to_farthings(), fewest.coins(t).to_counts().iter().sum()), (t, low));
+                assert_eq!((most.coins(t).to_farthings(), most.coins(t).to_counts().iter().sum()), (t, high));
+            }
+        }
+    }
+
+    #[test]
+    fn paying_from_a_wallet() {
+        let mut purse = Wallet { florins: 1, pennies: 5, ..Default::default() };
+        let payment = purse.pay(price!(1/11), PayStrategy::FewestCoins).unwrap();
+        assert_eq!(payment, Payment { tendered: Wallet { florins: 1, ..Default::default() }, change_due: price!(-/1) });
+        assert_eq!(purse.pay(price!(-/6), PayStrategy::SmallChangeFirst), None);
+        assert_eq!(purse, Wallet { pennies: 5, ..Default::default() });
+        assert_eq!(purse.pay(price!(0), PayStrategy::SmallChangeFirst).unwrap().tendered, Wallet::default());
+        let mut fortune = Wallet { ten_pounds: 2_000, ..Default::default() };
+        assert_eq!(fortune.pay(price!(15_000/-/-), PayStrategy::FewestCoins), None);
+        assert_eq!(fortune.pay(price!(9_995/-/-), PayStrategy::FewestCoins).unwrap().change_due, price!(5/-/-));
+    }
+
+    #[test]
+    fn change_preserving_keeps_denominations() {
+        let float = Wallet { florins: 1, shillings: 5, half_crowns: 5, sixpence: 5, ..Default::default() };
+        // 3s is a florin and a shilling or a half crown and a sixpence, but only the first takes
+        // the last florin.
+        assert_eq!(
+            float.change_preserving(price!(3/-), 1, CrownPolicy::default()),
+            Some(Wallet { half_crowns: 1, sixpence: 1, ..Default::default() }),
+        );
+        assert_eq!(
+            float.change_preserving(price!(3/-), 0, CrownPolicy::default()).map(|w| w.to_farthings()),
+            Some(price!(3/-).to_farthings()),
+        );
+        assert_eq!(float.change_preserving(price!(2/-/-), 0, CrownPolicy::default()), None);
+        let fortune = Wallet { ten_pounds: 2_000, ..Default::default() };
+        assert_eq!(fortune.change_preserving(price!(15_000/-/-), 0, CrownPolicy::default()), None);
+    }
+
+    #[test]
+    fn currency_prices_match_denominations() {
+        for (currency, farthings) in CURRENCIES.into_iter().zip(CURRENCIES_AS_FARTHINGS) {
+            assert_eq!(Price::from(currency).to_farthings(), farthings);
+            let same_value = Currency::from_farthings(farthings).unwrap();
+            assert_eq!(Price::from(same_value), Price::from(currency));
+        }
+    }
+
+    #[test]
+    fn all_currencies_in_value_order() {
+        assert!(Currency::ALL.windows(2).all(|w| w[0] < w[1] && w[0].value() <= w[1].value()));
+        assert_eq!(Currency::Farthing.value_in_halfpence(), 0);
+        assert_eq!(Currency::HalfCrown.value_in_halfpence(), 60);
+        for currency in Currency::ALL.into_iter().filter(|c| *c != Currency::Farthing) {
+            let same_value = Currency::from_halfpence(currency.value_in_halfpence()).unwrap();
+            assert_eq!(same_value.value(), currency.value());
+        }
+    }
+
+    #[test]
+    fn plausible_presets() {
+        let presets = ChangePolicy::decade_presets();
+        assert_eq!(presets.len(), 12);
+        assert!(presets.iter().all(|(_, p)| !p.allows(Currency::Crown) && p.currencies.contains(Currency::Crown)));
+        assert!(ChangePolicy::plausible_for_year(1950).allows(Currency::Farthing));
+        assert!(!ChangePolicy::plausible_for_year(1958).allows(Currency::Farthing));
+        assert!(ChangePolicy::plausible_for_year(1958).currencies.contains(Currency::Farthing));
+    }
+
+    #[test]
+    fn extra_denominations() {
+        let usual = Wallet { florins: 2, threepence: 1, pennies: 1, ..Default::default() };
+        assert_eq!(Wallet::change_for(price!(4/4), ChangePolicy::default()), usual);
+        let extras = Wallet { double_florins: 1, groats: 1, ..Default::default() };
+        assert_eq!(Wallet::change_for(price!(4/4), ExtraPolicy::Included), extras);
+        let notes = Wallet { one_pounds: 1, ten_shillings: 1, ..Default::default() };
+        assert_eq!(Wallet::change_for(price!(1/10/-), ExtraPolicy::Included), notes);
+        let mut wallet = Wallet::default();
+        wallet.add_currency(Currency::Sovereign);
+        wallet.add_currency(Currency::HalfSovereign);
+        assert_eq!(Price::from(wallet), price!(1/10/-));
+    }
+
+    #[test]
+    fn fallible_coin_change() {
+        assert_eq!(try_coin_change(&[5, 1], 2), Ok(vec![1, 1]));
+        assert_eq!(try_coin_change(&[2], 0), Ok(vec![]));
+        assert_eq!(try_coin_change(&[0, 1], 2), Err(ChangeError::ZeroCoin));
+        assert_eq!(try_coin_change(&[4, 6], 9), Err(ChangeError::Unreachable { target: 9 }));
+        assert_eq!(try_coin_change(&[1, 3, 4], 6), Ok(vec![3, 3]));
+        assert_eq!(try_coin_change_up_to(&[], 2000, 1000), Err(ChangeError::NoCoins));
+        let huge = 2_000_000_000 * FARTHINGS_PER_POUND;
+        assert_eq!(try_coin_change(&[1, 2], huge), Err(ChangeError::TooLarge { target: huge, max: DEFAULT_MAX_TARGET }));
+        assert!(minimal_changes(&[1, 2], huge).is_err());
+        assert_eq!(try_coin_change_with(&[1, huge], huge, Algorithm::Greedy), Ok(vec![huge]));
+        assert!(try_coin_change_with(&[1, huge], huge, Algorithm::Auto).is_err());
+    }
+
+    #[test]
+    fn change_over_the_table_limit() {
+        let fortune = price!(20_000/4/-);
+        let expected = Wallet { ten_pounds: 2_000, florins: 2, ..Default::default() };
+        assert_eq!(Wallet::change_for(fortune, ChangePolicy::default()), expected);
+        assert_eq!(Wallet::from(fortune), expected);
+        let coins = ChangePolicy::default().change_denominations();
+        let target = fortune.to_farthings();
+        assert_eq!(try_coin_change_with(&coins, target, Algorithm::Auto).map(|c| c.len()), Ok(2_002));
+        assert_eq!(unbounded_coin_change(&[96, 4800], target + 2), Err(ChangeError::Unreachable { target: target + 2 }));
+        assert!(coin_change(&[1, 3, 4], DEFAULT_MAX_TARGET + 6).ends_with(&[4, 3, 3]));
+    }
+
+    #[test]
+    fn algorithms_agree_on_canonical_coins() {
+        assert!(!is_canonical(&ChangePolicy::default().change_denominations()));
+        let denominations = ChangePolicy::from(CrownPolicy::Refused).change_denominations();
+        let denominations: Vec<usize> = denominations.into_iter().filter(|&d| d != 120).collect();
+        assert!(is_canonical(&denominations));
+        assert!(!is_canonical(&[2, 3]));
+        assert!(!is_canonical(&[1, 5, 7]));
+        for target in [0, 1, 47, 1000, 9999] {
+            let greedy = try_coin_change_with(&denominations, target, Algorithm::Greedy);
+            assert_eq!(greedy, try_coin_change_with(&denominations, target, Algorithm::DynamicProgramming));
+        }
+        assert_eq!(try_coin_change_with(&[4, 6], 8, Algorithm::Greedy), Err(ChangeError::Unreachable { target: 8 }));
+        let florins = try_coin_change_with(&ChangePolicy::default().change_denominations(), 192, Algorithm::Auto);
+        assert_eq!(florins, Ok(vec![96, 96]));
+    }
+
+    #[test]
+    fn other_integer_types() {
+        assert_eq!(try_coin_change_as(&[1u128, 2, 4, 12], 19), Ok(vec![12, 4, 2, 1]));
+        assert_eq!(try_coin_change_as(&[3i64, 5], 7), Err(ChangeError::Unreachable { target: 7 }));
+        assert_eq!(try_coin_change_as(&[1i16], -1), Err(ChangeError::Overflow));
+        assert_eq!(try_coin_change_as::<u16>(&[], 0), Err(ChangeError::NoCoins));
+    }
+
+    #[test]
+    fn change_maker_matches_coin_change() {
+        let coins = [1, 5, 7];
+        let maker = ChangeMaker::new(&coins, 60).unwrap();
+        assert_eq!(maker.max_target(), 60);
+        for target in 0..=60 {
+            assert_eq!(maker.change(target), try_coin_change(&coins, target));
+        }
+        let gappy = ChangeMaker::new(&[4, 6], 20).unwrap();
+        assert_eq!(gappy.change(7), Err(ChangeError::Unreachable { target: 7 }));
+        assert_eq!(gappy.fewest_coins(20), Some(4));
+        assert_eq!(gappy.fewest_coins(21), None);
+        assert_eq!(ChangeMaker::new(&[], 5), Err(ChangeError::NoCoins));
+    }
+
+    #[test]
+    fn indexing_by_currency() {
+        let mut wallet = Wallet::default();
+        for (i, &currency) in CURRENCIES.iter().enumerate() {
+            wallet[currency] = i + 1;
+        }
+        assert_eq!(wallet.to_counts(), core::array::from_fn(|i| i + 1));
+        assert!(CURRENCIES.iter().all(|&c| wallet[c] == wallet.to_counts()[c as usize]));
+        wallet.remove_currency(Currency::TenShilling);
+        assert_eq!((wallet.ten_shillings, wallet.half_sovereigns), (12, 12));
+        let mut empty = Wallet::default();
+        assert!(empty.try_remove_currency(Currency::TenPound).is_err());
+    }
+
+    #[test]
+    fn iterating_over_wallets() {
+        let wallet = Wallet { farthings: 2, crowns: 1, ten_pounds: 3, ..Default::default() };
+        assert_eq!(wallet.into_iter().len(), 6);
+        assert_eq!(wallet.into_iter().map(|c| Price::from(c).to_farthings()).sum::<usize>(), wallet.to_farthings());
+        assert_eq!(wallet.into_iter().last(), Some(Currency::TenPound));
+        assert_eq!(wallet.iter().count(), 3);
+        assert_eq!(Wallet::default().into_iter().next(), None);
+        assert_eq!(wallet.into_iter().collect::<Wallet>(), wallet);
+    }
+
+    #[test]
+    fn merging_and_splitting_wallets() {
+        let till = Wallet { farthings: 3, half_crowns: 2, one_pounds: 1, ..Default::default() };
+        let tin = Wallet { farthings: 1, crowns: 4, ..Default::default() };
+        let merged = till + tin;
+        assert_eq!(merged.to_farthings(), till.to_farthings() + tin.to_farthings());
+        assert_eq!(merged.checked_sub(tin), Some(till));
+        assert_eq!(till.checked_sub(merged), None);
+        let error = till.try_sub(merged).unwrap_err();
+        assert_eq!(error, WalletError::NotEnough { currency: Currency::Farthing, short: 1 });
+        assert_eq!(error.to_string(), "1 short of farthing");
+    }
+
+    #[test]
+    fn decompositions_add_up() {
+        let crown = Price::from(Currency::Crown).to_farthings();
+        let ways: Vec<Wallet> = Currency::Crown.decompositions(4).collect();
+        assert!(ways.iter().all(|w| w.to_farthings() == crown && w.into_iter().len() <= 4 && w.crowns == 0));
+        assert!(ways.contains(&Wallet { half_crowns: 2, ..Default::default() }));
+        assert!(ways.contains(&Wallet { florins: 2, shillings: 1, ..Default::default() }));
+        assert_eq!(Currency::Penny.decompositions(4).count(), 3);
+        assert_eq!(Currency::Sovereign.decompositions(2).count(), 3);
+        assert_eq!(Currency::Shilling.decompositions(1).next(), None);
+    }
+
+    #[test]
+    fn wallet_statistics() {
+        let empty = Wallet::default();
+        assert!(empty.is_empty());
+        assert_eq!((empty.largest(), empty.smallest()), (None, None));
+        assert!(empty.breakdown().is_empty());
+        let wallet = Wallet { farthings: 2, half_sovereigns: 1, ten_shillings: 3, ..Default::default() };
+        assert_eq!((wallet.coin_count(), wallet.note_count()), (3, 3));
+        assert_eq!(wallet.largest(), Some(Currency::TenShilling));
+        assert_eq!(wallet.breakdown().values().sum::<usize>(), wallet.len());
+        assert!(CURRENCIES.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn every_minimal_change() {
+        let halfpence = [1, 2, 6, 12, 24, 48, 60];
+        // 3s is a half crown and a sixpence, or a florin and a shilling.
+        let all: Vec<Vec<usize>> = minimal_changes(&halfpence, 72).unwrap().collect();
+        assert_eq!(all, vec![vec![60, 12], vec![48, 24]]);
+        assert_eq!(minimal_changes(&[5], 0).unwrap().collect::<Vec<_>>(), vec![Vec::<usize>::new()]);
+        assert_eq!(minimal_changes(&[1, 1], 2).unwrap().count(), 3);
+        assert!(matches!(minimal_changes(&[4], 6), Err(ChangeError::Unreachable { target: 6 })));
+    }
+
+    #[test]
+    fn farthings() {
+        let price = Price::with_farthings(0, 1, 47);
+        assert_eq!(price.to_string(), "£0 1s 11¾d");
+        assert_eq!(price + Price::from(Currency::Farthing), price!(2/-));
+        let change = Wallet { shillings: 1, sixpence: 1, threepence: 1, pennies: 2, halfpence: 1, farthings: 1, ..Default::default() };
+        assert_eq!(Wallet::from(price), change);
+        assert_eq!(Price::from(change), price);
+    }
+
+    #[test]
+    fn delta_direction() {
+        let owed = price!(2/-).delta(price!(2/6));
+        assert_eq!(owed, PriceDelta { direction: Direction::Owed, amount: price!(-/6) });
+        let due = price!(1/-/-).delta(price!(7/6));
+        assert_eq!(due.to_string(), "£0 12s 6d due");
+        assert!(price!(5/2).delta(price!(5/2)).is_settled());
+    }
 }
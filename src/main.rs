@@ -12,7 +12,181 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
 
-fn main() {
-    println!("Hello, world!");
+use coin_changing::{era::CurrencySet, ChangePolicy, Currency, PayStrategy, Price, Wallet};
+
+const HELP: &str = "\
+Prices are written like 2/6, 1/-/-, or £1 2s 6d. Operators need spaces around them.
+  a = 2/6           store a price in a variable
+  a + 1/3 - b * 2   work out a price
+  change <price>    the fewest coins making up a price
+  wallet            show what's in the wallet
+  add [n] <coin>    put coins in the wallet, e.g. add 3 florins or add tanner
+  remove [n] <coin> take coins out of the wallet
+  pay <price>       pay from the wallet
+  help              show this
+  quit              leave";
+
+/// The state kept between lines of the REPL.
+#[derive(Debug, Default)]
+struct Session {
+    variables: HashMap<String, Price>,
+    wallet: Wallet,
+}
+
+impl Session {
+    /// Run one line, returning what to print.
+    fn run(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match command {
+            "" => Ok(String::new()),
+            "help" => Ok(HELP.to_string()),
+            "wallet" => Ok(self.wallet.to_string()),
+            "change" => Ok(Wallet::change_for(self.evaluate(rest)?, ChangePolicy::default()).to_string()),
+            "add" | "remove" => {
+                let (count, currency) = parse_coins(rest)?;
+                if command == "add" {
+                    self.wallet[currency] += count;
+                } else {
+                    let mut taken = Wallet::default();
+                    taken[currency] = count;
+                    self.wallet = self.wallet.try_sub(taken).map_err(|e| e.to_string())?;
+                }
+                Ok(self.wallet.to_string())
+            }
+            "pay" => {
+                let price = self.evaluate(rest)?;
+                let payment = self.wallet.pay(price, PayStrategy::default()).ok_or("not enough in the wallet")?;
+                Ok(format!("handed over {}, {} back", payment.tendered, payment.change_due))
+            }
+            _ => match line.split_once(" = ") {
+                Some((name, expression)) => {
+                    let name = name.trim();
+                    if !is_name(name) {
+                        return Err(format!("{:?} can't be a variable name", name));
+                    }
+                    let price = self.evaluate(expression)?;
+                    self.variables.insert(name.to_string(), price);
+                    Ok(format!("{} = {}", name, price))
+                }
+                None => self.evaluate(line).map(|price| price.to_string()),
+            },
+        }
+    }
+
+    /// Work out a sum like `a + 1/3 - b * 2`, left to right.
+    fn evaluate(&self, expression: &str) -> Result<Price, String> {
+        let mut tokens = expression.split_whitespace();
+        let mut total = self.term(tokens.next().ok_or("expected a price")?)?;
+        while let Some(operator) = tokens.next() {
+            let operand = tokens.next().ok_or_else(|| format!("expected something after {}", operator))?;
+            total = match operator {
+                "+" => total + self.term(operand)?,
+                "-" => total.checked_sub(self.term(operand)?).ok_or("prices can't go negative")?,
+                "*" => {
+                    let n = operand.parse::<usize>().map_err(|_| format!("can't multiply by {}", operand))?;
+                    total.checked_mul(n).ok_or("that's too much money to work with")?
+                }
+                _ => return Err(format!("unknown operator {}", operator)),
+            };
+        }
+        Ok(total)
+    }
+
+    /// A variable or a price.
+    fn term(&self, token: &str) -> Result<Price, String> {
+        match self.variables.get(token) {
+            Some(&price) => Ok(price),
+            None if is_name(token) => Err(format!("no variable called {}", token)),
+            None => token.parse().map_err(|e| format!("{}", e)),
+        }
+    }
+}
+
+/// Whether a token is a variable name rather than a price.
+fn is_name(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_alphabetic()) && token.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Read "3 florins" or just "half crown". Nicknames like "bob" work too.
+fn parse_coins(s: &str) -> Result<(usize, Currency), String> {
+    let s = s.trim();
+    let (count, name) = match s.split_once(' ').map(|(count, name)| (count.parse(), name)) {
+        Some((Ok(count), name)) => (count, name),
+        _ => (1, s),
+    };
+    let wanted = name.to_lowercase();
+    CurrencySet::all()
+        .iter()
+        .find(|c| [Some(c.name()), Some(c.plural()), c.nickname()].contains(&Some(wanted.as_str())))
+        .map(|c| (count, c))
+        .ok_or_else(|| format!("no coin called {}", name))
+}
+
+fn repl() -> io::Result<()> {
+    let mut session = Session::default();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    write!(stdout, "> ")?;
+    stdout.flush()?;
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if matches!(line.trim(), "quit" | "exit") {
+            break;
+        }
+        match session.run(&line) {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => writeln!(stdout, "{}", output)?,
+            Err(error) => writeln!(stdout, "error: {}", error)?,
+        }
+        write!(stdout, "> ")?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    match std::env::args().nth(1).as_deref() {
+        Some("repl") => repl(),
+        _ => {
+            println!("usage: coin_changing repl");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coin_changing::price;
+
+    #[test]
+    fn sums_and_variables() {
+        let mut session = Session::default();
+        assert_eq!(session.run("a = 2/6"), Ok("a = £0 2s 6d".to_string()));
+        assert_eq!(session.run("a + 1/3 * 2"), Ok(price!(7/6).to_string()));
+        assert_eq!(session.run("a - 1/-/-"), Err("prices can't go negative".to_string()));
+        assert_eq!(session.run(&format!("a * {}", usize::MAX)), Err("that's too much money to work with".to_string()));
+        assert!(session.run("b + 1/-").is_err());
+        assert_eq!(session.run("change a"), Ok("1 half crown (total £0 2s 6d)".to_string()));
+    }
+
+    #[test]
+    fn wallet_commands() {
+        let mut session = Session::default();
+        session.run("add 3 florins").unwrap();
+        session.run("add half a dollar").unwrap();
+        assert_eq!(session.wallet, Wallet { florins: 3, half_crowns: 1, ..Default::default() });
+        assert!(session.run("remove 2 crowns").is_err());
+        assert!(session.run("add groot").is_err());
+        session.run("remove 2 florins").unwrap();
+        assert_eq!(session.run("pay 2/6"), Ok("handed over 1 half crown (total £0 2s 6d), £0 0s 0d back".to_string()));
+        assert!(session.run("pay 5/-").is_err());
+        assert_eq!(session.wallet, Wallet { florins: 1, ..Default::default() });
+    }
 }
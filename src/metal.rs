@@ -0,0 +1,202 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! What coins weighed and were made of. Masses are the standard ones set by the Mint, in
+//! milligrams so everything stays in whole numbers.
+
+use crate::{ChangePolicy, Currency, Wallet};
+
+/// What a coin (or note) is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metal {
+    /// The copper coins, replaced by bronze in 1860.
+    Copper,
+    Bronze,
+    /// Sterling silver until 1919, then half silver until 1946.
+    Silver,
+    /// What replaced silver from 1947.
+    Cupronickel,
+    /// The twelve-sided threepence.
+    NickelBrass,
+    Gold,
+    Paper,
+}
+
+/// The make-up of a coin or note struck in a particular year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoinSpec {
+    pub metal: Metal,
+    pub milligrams: u32,
+    /// Parts per thousand of silver or gold, or 0 for base metal.
+    pub fineness: u32,
+}
+
+impl CoinSpec {
+    /// How many milligrams of silver are in the coin.
+    pub fn silver_milligrams(&self) -> u32 {
+        match self.metal {
+            Metal::Silver => self.milligrams * self.fineness / 1000,
+            _ => 0,
+        }
+    }
+}
+
+/// The silver coins' fineness in a given year, or [`None`] once they were cupronickel.
+fn silver_fineness(year: u32) -> Option<u32> {
+    match year {
+        ..=1919 => Some(925),
+        1920..=1946 => Some(500),
+        _ => None,
+    }
+}
+
+impl Currency {
+    /// What this coin or note weighed and was made of when struck in `year`. Both silver and
+    /// brass threepences were around from 1937 to 1941; the brass one is given from 1942. Notes
+    /// are counted as weighing about a gram.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::Currency;
+    /// use coin_changing::metal::Metal;
+    ///
+    /// assert_eq!(Currency::Penny.spec(1850).metal, Metal::Copper);
+    /// assert_eq!(Currency::Penny.spec(1950).milligrams, 9_450);
+    /// assert_eq!(Currency::Florin.spec(1930).fineness, 500);
+    /// assert_eq!(Currency::Florin.spec(1950).metal, Metal::Cupronickel);
+    /// ```
+    pub fn spec(&self, year: u32) -> CoinSpec {
+        let base = |metal, milligrams| CoinSpec { metal, milligrams, fineness: 0 };
+        let copper_or_bronze = |copper, bronze| match year {
+            ..=1859 => base(Metal::Copper, copper),
+            _ => base(Metal::Bronze, bronze),
+        };
+        let silver = |milligrams| match silver_fineness(year) {
+            Some(fineness) => CoinSpec { metal: Metal::Silver, milligrams, fineness },
+            None => base(Metal::Cupronickel, milligrams),
+        };
+        let gold = |milligrams| CoinSpec { metal: Metal::Gold, milligrams, fineness: 917 };
+        match self {
+            Currency::Farthing => copper_or_bronze(4_700, 2_830),
+            Currency::Halfpenny => copper_or_bronze(9_400, 5_670),
+            Currency::Penny => copper_or_bronze(18_800, 9_450),
+            Currency::Threepence if year >= 1942 => base(Metal::NickelBrass, 6_800),
+            Currency::Threepence => silver(1_410),
+            Currency::Groat => silver(1_890),
+            Currency::Sixpence => silver(2_830),
+            Currency::Shilling => silver(5_655),
+            Currency::Florin => silver(11_310),
+            Currency::HalfCrown => silver(14_140),
+            Currency::DoubleFlorin => silver(22_620),
+            Currency::Crown => silver(28_280),
+            Currency::HalfSovereign => gold(3_990),
+            Currency::Sovereign => gold(7_990),
+            Currency::TenShilling | Currency::OnePound | Currency::FivePound | Currency::TenPound => {
+                base(Metal::Paper, 1_000)
+            }
+        }
+    }
+}
+
+impl ChangePolicy {
+    /// What a coin given as change under this policy in `year` is made of. This is
+    /// [`Currency::spec`], except that while both silver and brass threepences were about, the
+    /// one given is the policy's [`threepence`](ChangePolicy::threepence) preference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ ChangePolicy, Currency };
+    /// use coin_changing::metal::Metal;
+    ///
+    /// let threepence = |year| ChangePolicy::plausible_for_year(year).spec(Currency::Threepence, year).metal;
+    /// assert_eq!(threepence(1939), Metal::Silver);
+    /// assert_eq!(threepence(1943), Metal::NickelBrass);
+    /// let mut scottish = ChangePolicy::plausible_for_year(1943);
+    /// scottish.threepence = Some(Metal::Silver);
+    /// assert_eq!(scottish.spec(Currency::Threepence, 1943).metal, Metal::Silver);
+    /// assert_eq!(scottish.spec(Currency::Threepence, 1950).metal, Metal::NickelBrass);
+    /// ```
+    pub fn spec(&self, currency: Currency, year: u32) -> CoinSpec {
+        let both_about = (1937..=1945).contains(&year);
+        match self.threepence {
+            Some(Metal::Silver) if currency == Currency::Threepence && both_about => Currency::Threepence.spec(1941),
+            Some(Metal::NickelBrass) if currency == Currency::Threepence && both_about => {
+                Currency::Threepence.spec(1942)
+            }
+            _ => currency.spec(year),
+        }
+    }
+
+    /// How much some change given under this policy in `year` weighs in milligrams, with each
+    /// coin's make-up from [`ChangePolicy::spec`].
+    pub fn weight(&self, change: &Wallet, year: u32) -> u64 {
+        change.iter().map(|(c, count)| self.spec(c, year).milligrams as u64 * count as u64).sum()
+    }
+}
+
+impl Wallet {
+    /// How much everything in the wallet weighs in milligrams, if it was all struck in `year`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::Wallet;
+    ///
+    /// // £5 in half crowns.
+    /// let bag = Wallet { half_crowns: 40, ..Default::default() };
+    /// assert_eq!(bag.weight(1950), 565_600);
+    /// assert_eq!(bag.silver_content(1950), 0);
+    /// assert_eq!(bag.silver_content(1900), 523_160);
+    /// ```
+    pub fn weight(&self, year: u32) -> u64 {
+        self.iter().map(|(c, count)| c.spec(year).milligrams as u64 * count as u64).sum()
+    }
+
+    /// How many milligrams of silver are in the wallet, if it was all struck in `year`.
+    pub fn silver_content(&self, year: u32) -> u64 {
+        self.iter().map(|(c, count)| c.spec(year).silver_milligrams() as u64 * count as u64).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CURRENCIES;
+
+    #[test]
+    fn silver_by_year() {
+        // Silver coins were struck by weight, so a shilling's worth always weighed the same.
+        for year in [1850, 1930, 1960] {
+            assert_eq!(Currency::Florin.spec(year).milligrams, 2 * Currency::Shilling.spec(year).milligrams);
+        }
+        assert_eq!(Currency::Shilling.spec(1900).silver_milligrams(), 5_230);
+        assert_eq!(Currency::Shilling.spec(1947).silver_milligrams(), 0);
+        assert_eq!(Currency::Threepence.spec(1960).metal, Metal::NickelBrass);
+        assert!(CURRENCIES.iter().all(|c| c.is_note() == (c.spec(1950).metal == Metal::Paper)));
+        assert_eq!(Wallet::default().weight(1900), 0);
+    }
+
+    #[test]
+    fn threepence_preference() {
+        let threepences = Wallet { threepence: 2, ..Default::default() };
+        let early = ChangePolicy::plausible_for_year(1938);
+        let late = ChangePolicy::plausible_for_year(1944);
+        assert_eq!(early.weight(&threepences, 1940), 2 * 1_410);
+        assert_eq!(late.weight(&threepences, 1940), 2 * 6_800);
+        assert_eq!(ChangePolicy::default().weight(&threepences, 1940), threepences.weight(1940));
+        assert_eq!(early.spec(Currency::Threepence, 1930), Currency::Threepence.spec(1930));
+    }
+}
@@ -0,0 +1,133 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Converting prices to and from a whole number of some small unit, for storing in a database or
+//! handing to another money library. The unit always has to be picked explicitly, and anything
+//! which doesn't convert exactly is an error rather than being rounded.
+
+use core::fmt::Display;
+
+use crate::{Price, FARTHINGS_PER_HALFPENNY, FARTHINGS_PER_PENNY, FARTHINGS_PER_POUND};
+
+/// The unit a price is counted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MinorUnit {
+    Farthings,
+    Halfpence,
+    Pence,
+    /// Thousandths of a pound, as in the decimal schemes floated long before 1971. A mil is 24/25
+    /// of a farthing, so only multiples of 6d convert exactly.
+    Mils,
+}
+
+impl MinorUnit {
+    /// How many farthings make `per` of the unit, as a fraction `(farthings, per)`.
+    fn in_farthings(&self) -> (u64, u64) {
+        match self {
+            MinorUnit::Farthings => (1, 1),
+            MinorUnit::Halfpence => (FARTHINGS_PER_HALFPENNY as u64, 1),
+            MinorUnit::Pence => (FARTHINGS_PER_PENNY as u64, 1),
+            MinorUnit::Mils => (FARTHINGS_PER_POUND as u64, 1000),
+        }
+    }
+}
+
+/// Why a price couldn't be converted to or from minor units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinorUnitError {
+    /// The amount is too big for the type it's going into.
+    Overflow,
+    /// The amount isn't a whole number of the unit it's going into, e.g. a farthing in pence.
+    Inexact,
+}
+
+impl Display for MinorUnitError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MinorUnitError::Overflow => write!(f, "amount too big to convert"),
+            MinorUnitError::Inexact => write!(f, "amount isn't a whole number of the unit"),
+        }
+    }
+}
+
+impl core::error::Error for MinorUnitError {}
+
+/// Work out `value * numerator / denominator`, as long as it's exact and fits.
+fn scale(value: u64, numerator: u64, denominator: u64) -> Result<u64, MinorUnitError> {
+    let scaled = value.checked_mul(numerator).ok_or(MinorUnitError::Overflow)?;
+    if scaled % denominator != 0 {
+        return Err(MinorUnitError::Inexact);
+    }
+    Ok(scaled / denominator)
+}
+
+impl Price {
+    /// The price as a whole number of `unit`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    /// use coin_changing::minor::{ MinorUnit, MinorUnitError };
+    ///
+    /// assert_eq!(price!(1/0/6).minor_units(MinorUnit::Pence), Ok(246));
+    /// assert_eq!(price!(1/0/6).minor_units(MinorUnit::Mils), Ok(1025));
+    /// assert_eq!(Price::from_farthings(1).minor_units(MinorUnit::Halfpence), Err(MinorUnitError::Inexact));
+    /// ```
+    pub fn minor_units(&self, unit: MinorUnit) -> Result<u64, MinorUnitError> {
+        let farthings = u64::try_from(self.to_farthings()).map_err(|_| MinorUnitError::Overflow)?;
+        let (farthings_per, per) = unit.in_farthings();
+        scale(farthings, per, farthings_per)
+    }
+
+    /// The price of a whole number of `unit`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    /// use coin_changing::minor::{ MinorUnit, MinorUnitError };
+    ///
+    /// assert_eq!(Price::from_minor_units(246, MinorUnit::Pence), Ok(price!(1/0/6)));
+    /// assert_eq!(Price::from_minor_units(1, MinorUnit::Mils), Err(MinorUnitError::Inexact));
+    /// ```
+    pub fn from_minor_units(units: u64, unit: MinorUnit) -> Result<Price, MinorUnitError> {
+        let (farthings_per, per) = unit.in_farthings();
+        let farthings = scale(units, farthings_per, per)?;
+        let farthings = usize::try_from(farthings).map_err(|_| MinorUnitError::Overflow)?;
+        Ok(Price::from_farthings(farthings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn round_trips() {
+        let units = [MinorUnit::Farthings, MinorUnit::Halfpence, MinorUnit::Pence, MinorUnit::Mils];
+        for price in [price!(0), price!(-/6), price!(3/7/6), price!(1000/-/-)] {
+            for unit in units {
+                let converted = price.minor_units(unit).unwrap();
+                assert_eq!(Price::from_minor_units(converted, unit), Ok(price));
+            }
+        }
+        assert_eq!(price!(-/3).minor_units(MinorUnit::Mils), Err(MinorUnitError::Inexact));
+        assert_eq!(Price::from_minor_units(u64::MAX, MinorUnit::Pence), Err(MinorUnitError::Overflow));
+        let most = Price::from_minor_units(u64::MAX, MinorUnit::Farthings).unwrap();
+        assert_eq!(most.minor_units(MinorUnit::Farthings), Ok(u64::MAX));
+        assert_eq!(most.minor_units(MinorUnit::Mils), Err(MinorUnitError::Overflow));
+    }
+}
@@ -0,0 +1,281 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Every notation prices can be written in, so front-ends can list them (e.g. in a picker or a
+//! help screen) without keeping their own copy.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{
+    farthings_to_pence,
+    parse::{parse_lsd, IncrementalParser, ParseErrorKind, ParsePriceError},
+    words::{parse_words, write_words},
+    Price, FARTHINGS_PER_SHILLING, SHILLINGS_PER_GUINEA,
+};
+
+/// A way of writing a price down.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::notation::Notation;
+///
+/// for notation in Notation::ALL {
+///     for example in notation.examples() {
+///         let price = notation.parse(example).unwrap();
+///         assert_eq!(notation.format(price), *example);
+///     }
+/// }
+/// assert_eq!(Notation::Slash.format(price!(1/2/6)), "1/2/6");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// Shillings and pence separated by a slash, with pounds in front if there are any, and a
+    /// dash for zero: `"7/6"`, `"1/-/6"`.
+    Slash,
+    /// Pounds, shillings, and pence each marked with their unit: `"£1 2s 6d"`. This is how
+    /// [`Price`] is displayed.
+    Lsd,
+    /// A whole number of guineas: `"5 gns"`. Amounts which aren't whole guineas are written in
+    /// £sd notation instead.
+    Guineas,
+    /// Written out in English words, like on a cheque: `"one pound, two shillings and sixpence"`.
+    /// Reading also understands everyday forms like `"one and six"`, see [`Price::from_english`].
+    Words,
+}
+
+impl Notation {
+    /// Every notation, in the order they should be offered.
+    pub const ALL: [Notation; 4] = [Notation::Slash, Notation::Lsd, Notation::Guineas, Notation::Words];
+
+    /// A short name to show to users.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Notation::Slash => "Slash",
+            Notation::Lsd => "£sd",
+            Notation::Guineas => "Guineas",
+            Notation::Words => "Words",
+        }
+    }
+
+    /// Some example prices written in this notation. Each one reads back the same way it's
+    /// written by [`Notation::format`].
+    pub fn examples(&self) -> &'static [&'static str] {
+        match self {
+            Notation::Slash => &["7/6", "1/2/6", "-/4½", "10/-", "1/-/-"],
+            Notation::Lsd => &["£1 2s 6d", "£0 7s 6d", "£0 0s 4½d"],
+            Notation::Guineas => &["5 gns", "1 gn"],
+            Notation::Words => &[
+                "seven shillings and sixpence",
+                "one pound, two shillings and sixpence",
+                "fourpence halfpenny",
+                "nothing",
+            ],
+        }
+    }
+
+    /// Write a price in this notation.
+    pub fn format(&self, price: Price) -> String {
+        let mut out = String::new();
+        self.write(&mut out, price).unwrap();
+        out
+    }
+
+    /// Write a price in this notation straight into `out`, without allocating.
+    pub fn write(&self, out: &mut impl Write, price: Price) -> core::fmt::Result {
+        match self {
+            Notation::Slash => {
+                let (pence, farthings) = farthings_to_pence(price.farthings);
+                let fraction = ["", "¼", "½", "¾"][farthings];
+                if price.pounds > 0 {
+                    write!(out, "{}/", price.pounds)?;
+                }
+                match price.shillings {
+                    0 => out.write_str("-/")?,
+                    shillings => write!(out, "{}/", shillings)?,
+                }
+                match (pence, fraction) {
+                    (0, "") => out.write_str("-"),
+                    (0, fraction) => out.write_str(fraction),
+                    (pence, fraction) => write!(out, "{}{}", pence, fraction),
+                }
+            }
+            Notation::Lsd => write!(out, "{}", price),
+            Notation::Guineas => write!(out, "{}", price.in_guineas()),
+            Notation::Words => write_words(out, price),
+        }
+    }
+
+    /// Read a price written in this notation. Leading and trailing whitespace is ignored, and
+    /// error positions count characters from the start of `s`.
+    pub fn parse(&self, s: &str) -> Result<Price, ParsePriceError> {
+        let offset = s.chars().take_while(|c| c.is_whitespace()).count();
+        let trimmed = s.trim();
+        let result = match self {
+            Notation::Slash => {
+                let mut parser = IncrementalParser::new();
+                parser.push_str(trimmed).and_then(|_| parser.finish())
+            }
+            Notation::Lsd => parse_lsd(trimmed),
+            Notation::Guineas => match trimmed.strip_suffix("gns").or(trimmed.strip_suffix("gn")) {
+                Some(number) => parse_guineas(number.trim_end()),
+                None => parse_lsd(trimmed),
+            },
+            Notation::Words => parse_words(trimmed),
+        };
+        result.map_err(|e| ParsePriceError { position: e.position + offset, ..e })
+    }
+}
+
+impl Price {
+    /// Write the price in the given notation, e.g. for a receipt or a label. This is the same as
+    /// [`Notation::format`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    /// use coin_changing::notation::Notation;
+    ///
+    /// assert_eq!(price!(3/6).format(Notation::Slash), "3/6");
+    /// assert_eq!(price!(1/-).format(Notation::Slash), "1/-");
+    /// assert_eq!(price!(1/2/6).format(Notation::Lsd), "£1 2s 6d");
+    /// assert_eq!(Price::from_guineas(5).format(Notation::Guineas), "5 gns");
+    /// assert_eq!(price!(2/10/-).format(Notation::Words), "two pounds and ten shillings");
+    /// ```
+    pub fn format(&self, notation: Notation) -> String {
+        notation.format(*self)
+    }
+}
+
+/// Parse the number in front of "gns".
+fn parse_guineas(s: &str) -> Result<Price, ParsePriceError> {
+    let mut guineas: usize = 0;
+    if s.is_empty() {
+        return Err(ParsePriceError { position: 0, kind: ParseErrorKind::UnexpectedEnd });
+    }
+    for (position, c) in s.chars().enumerate() {
+        let digit = c.to_digit(10).ok_or(ParsePriceError { position, kind: ParseErrorKind::UnexpectedChar(c) })?;
+        guineas = guineas
+            .checked_mul(10)
+            .and_then(|g| g.checked_add(digit as usize))
+            .filter(|g| g.checked_mul(SHILLINGS_PER_GUINEA * FARTHINGS_PER_SHILLING).is_some())
+            .ok_or(ParsePriceError { position, kind: ParseErrorKind::Overflow })?;
+    }
+    Ok(Price::from_guineas(guineas))
+}
+
+/// Writes lots of prices one after another into a single buffer, e.g. for a report or a CSV
+/// column, without allocating a string per price.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::notation::{ BatchFormatter, Notation };
+///
+/// let mut batch = BatchFormatter::with_capacity(64, Notation::Slash, ",");
+/// batch.extend([price!(7/6), price!(1/-/-), price!(-/3)]);
+/// assert_eq!(batch.as_str(), "7/6,1/-/-,-/3");
+/// assert_eq!(batch.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchFormatter<'a> {
+    buffer: String,
+    notation: Notation,
+    separator: &'a str,
+    count: usize,
+}
+
+impl<'a> BatchFormatter<'a> {
+    pub fn new(notation: Notation, separator: &'a str) -> Self {
+        Self::with_capacity(0, notation, separator)
+    }
+
+    /// Start with room for `capacity` bytes, so the buffer doesn't need to grow along the way.
+    pub fn with_capacity(capacity: usize, notation: Notation, separator: &'a str) -> Self {
+        BatchFormatter { buffer: String::with_capacity(capacity), notation, separator, count: 0 }
+    }
+
+    /// Add a price, after a separator if it isn't the first.
+    pub fn push(&mut self, price: Price) {
+        if self.count > 0 {
+            self.buffer.push_str(self.separator);
+        }
+        // Writing to a `String` never fails.
+        self.notation.write(&mut self.buffer, price).unwrap();
+        self.count += 1;
+    }
+
+    /// How many prices have been written.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Empty the buffer, keeping its allocation for the next batch.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.count = 0;
+    }
+
+    pub fn into_string(self) -> String {
+        self.buffer
+    }
+}
+
+impl Extend<Price> for BatchFormatter<'_> {
+    fn extend<T: IntoIterator<Item = Price>>(&mut self, iter: T) {
+        for price in iter {
+            self.push(price);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn slash_and_guineas() {
+        assert_eq!(Notation::Slash.format(Price::default()), "-/-");
+        assert_eq!(Notation::Slash.format(price!(2/-/-)), "2/-/-");
+        assert_eq!(Notation::Slash.format(Price::from_farthings(3)), "-/¾");
+        assert_eq!(Notation::Guineas.parse(" 21 gns"), Ok(price!(22/1/-)));
+        assert_eq!(Notation::Guineas.parse("£1 0s 0d"), Ok(price!(1/-/-)));
+        let error = Notation::Guineas.parse("2x gns").unwrap_err();
+        assert_eq!((error.position, error.kind), (1, ParseErrorKind::UnexpectedChar('x')));
+    }
+
+    #[test]
+    fn batches_reuse_the_buffer() {
+        let mut batch = BatchFormatter::new(Notation::Lsd, "\n");
+        batch.extend([price!(1/-), price!(-/6)]);
+        assert_eq!(batch.as_str(), "£0 1s 0d\n£0 0s 6d");
+        batch.clear();
+        assert!(batch.is_empty());
+        batch.push(Price::from_guineas(2));
+        assert_eq!(batch.into_string(), "£2 2s 0d");
+    }
+}
@@ -0,0 +1,541 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing prices from text.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt::Display, str::FromStr};
+
+use crate::{HalfpennyPolicy, Price, PENCE_PER_SHILLING, SHILLINGS_PER_POUND, farthings_to_pence, pence_to_farthings};
+
+/// What went wrong while parsing a price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A character which can't appear at this point.
+    UnexpectedChar(char),
+    /// The input ended before a complete price was given, e.g. `"1/"`.
+    UnexpectedEnd,
+    /// More than three slash separated fields were given.
+    TooManyFields,
+    /// The pence were 12 or more.
+    PenceOutOfRange,
+    /// The shillings were 20 or more when pounds were also given.
+    ShillingsOutOfRange,
+    /// A number was too big to fit.
+    Overflow,
+    /// A fraction of a penny was given after the halfpenny was withdrawn.
+    HalfpennyWithdrawn,
+}
+
+/// An error from parsing a price, along with the (character, not byte) position in the input
+/// where it happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsePriceError {
+    pub position: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl Display for ParsePriceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            ParseErrorKind::UnexpectedChar(c) => {
+                write!(f, "unexpected character '{}' at position {}", c, self.position)
+            }
+            ParseErrorKind::UnexpectedEnd => write!(f, "price ended early at position {}", self.position),
+            ParseErrorKind::TooManyFields => {
+                write!(f, "too many fields at position {}", self.position)
+            }
+            ParseErrorKind::PenceOutOfRange => {
+                write!(f, "pence must be less than 12 at position {}", self.position)
+            }
+            ParseErrorKind::ShillingsOutOfRange => {
+                write!(f, "shillings must be less than 20 at position {}", self.position)
+            }
+            ParseErrorKind::Overflow => write!(f, "number too large at position {}", self.position),
+            ParseErrorKind::HalfpennyWithdrawn => {
+                write!(f, "halfpenny used after its withdrawal at position {}", self.position)
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParsePriceError {}
+
+/// How the input typed into an [`IncrementalParser`] reads so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    /// Nothing has been typed yet.
+    Empty,
+    /// The input ends in a slash, so more is expected. The price is what it would be if the
+    /// missing field were a dash, e.g. `"1/4/"` reads as `1/4/-`.
+    Incomplete(Price),
+    /// The input is a valid price as it stands (though more may still be typed).
+    Complete(Price),
+    /// The input can't become a valid price.
+    Invalid(ParsePriceError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Empty,
+    Dash,
+    /// A number, along with any fraction of a penny after it in farthings.
+    Number { value: usize, fraction: usize },
+}
+
+impl Field {
+    fn value(&self) -> usize {
+        match self {
+            Field::Number { value, .. } => *value,
+            _ => 0,
+        }
+    }
+
+    fn fraction(&self) -> usize {
+        match self {
+            Field::Number { fraction, .. } => *fraction,
+            _ => 0,
+        }
+    }
+}
+
+/// The number of farthings a fraction character stands for.
+fn fraction_farthings(c: char) -> Option<usize> {
+    match c {
+        '¼' => Some(1),
+        '½' => Some(2),
+        '¾' => Some(3),
+        _ => None,
+    }
+}
+
+/// A parser for slash notation (`"1/4/6"`, `"5/-"`, `"-/3½"`, `"1/11¾"`) which is fed one character at a
+/// time, so a price can be shown live as it's being typed.
+///
+/// One field is read as pence, two as shillings and pence, and three as pounds, shillings, and
+/// pence. A dash stands for zero.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::parse::{ IncrementalParser, Interpretation };
+///
+/// let mut parser = IncrementalParser::new();
+/// parser.push_str("1/4").unwrap();
+/// assert_eq!(parser.current_interpretation(), Interpretation::Complete(price!(1/4)));
+/// parser.push_char('/').unwrap();
+/// assert_eq!(parser.current_interpretation(), Interpretation::Incomplete(price!(1/4/-)));
+/// parser.push_char('6').unwrap();
+/// assert_eq!(parser.finish(), Ok(price!(1/4/6)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncrementalParser {
+    input: String,
+    len: usize,
+    /// Each field along with the position it starts at.
+    fields: Vec<(usize, Field)>,
+    error: Option<ParsePriceError>,
+}
+
+impl Default for IncrementalParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalParser {
+    pub fn new() -> Self {
+        IncrementalParser { input: String::new(), len: 0, fields: vec![(0, Field::Empty)], error: None }
+    }
+
+    /// Everything typed so far.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Feed in the next character. Once an error has happened, it's returned for every following
+    /// character too, until it's removed with [`IncrementalParser::pop_char`].
+    pub fn push_char(&mut self, c: char) -> Result<(), ParsePriceError> {
+        let position = self.len;
+        self.input.push(c);
+        self.len += 1;
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let fail = |kind| ParsePriceError { position, kind };
+        let (_, field) = self.fields.last_mut().unwrap();
+        let result = match (c, *field) {
+            ('0'..='9', Field::Empty) => {
+                *field = Field::Number { value: c as usize - '0' as usize, fraction: 0 };
+                Ok(())
+            }
+            ('0'..='9', Field::Number { value, fraction: 0 }) => {
+                match value.checked_mul(10).and_then(|v| v.checked_add(c as usize - '0' as usize)) {
+                    Some(value) => {
+                        *field = Field::Number { value, fraction: 0 };
+                        Ok(())
+                    }
+                    None => Err(fail(ParseErrorKind::Overflow)),
+                }
+            }
+            ('-', Field::Empty) => {
+                *field = Field::Dash;
+                Ok(())
+            }
+            ('¼' | '½' | '¾', Field::Empty) => {
+                *field = Field::Number { value: 0, fraction: fraction_farthings(c).unwrap() };
+                Ok(())
+            }
+            ('¼' | '½' | '¾', Field::Number { value, fraction: 0 }) => {
+                *field = Field::Number { value, fraction: fraction_farthings(c).unwrap() };
+                Ok(())
+            }
+            ('/', Field::Dash | Field::Number { .. }) => {
+                if self.fields.len() == 3 {
+                    Err(fail(ParseErrorKind::TooManyFields))
+                } else {
+                    self.fields.push((position + 1, Field::Empty));
+                    Ok(())
+                }
+            }
+            _ => Err(fail(ParseErrorKind::UnexpectedChar(c))),
+        };
+        if let Err(error) = result {
+            self.error = Some(error);
+        }
+        result
+    }
+
+    /// Feed in several characters, returning the first error if there was one.
+    pub fn push_str(&mut self, s: &str) -> Result<(), ParsePriceError> {
+        let mut result = Ok(());
+        for c in s.chars() {
+            let pushed = self.push_char(c);
+            result = result.and(pushed);
+        }
+        result
+    }
+
+    /// Remove the last character, like pressing backspace.
+    pub fn pop_char(&mut self) -> Option<char> {
+        let mut input = core::mem::take(&mut self.input);
+        let popped = input.pop();
+        *self = Self::new();
+        for c in input.chars() {
+            // Errors are kept in `self.error`, we only need to replay the input.
+            let _ = self.push_char(c);
+        }
+        popped
+    }
+
+    /// How the input reads so far.
+    pub fn current_interpretation(&self) -> Interpretation {
+        if let Some(error) = self.error {
+            return Interpretation::Invalid(error);
+        }
+        let (pounds, shillings, pence) = match self.fields.as_slice() {
+            [(_, Field::Empty)] => return Interpretation::Empty,
+            [pence] => ((0, Field::Dash), (0, Field::Dash), *pence),
+            [shillings, pence] => ((0, Field::Dash), *shillings, *pence),
+            [pounds, shillings, pence] => (*pounds, *shillings, *pence),
+            _ => unreachable!("there are always between one and three fields"),
+        };
+        for (position, field) in [pounds, shillings] {
+            if field.fraction() > 0 {
+                let (offset, c) = self
+                    .input
+                    .chars()
+                    .skip(position)
+                    .enumerate()
+                    .find(|&(_, c)| fraction_farthings(c).is_some())
+                    .unwrap();
+                let kind = ParseErrorKind::UnexpectedChar(c);
+                return Interpretation::Invalid(ParsePriceError { position: position + offset, kind });
+            }
+        }
+        if pence.1.value() >= PENCE_PER_SHILLING {
+            let kind = ParseErrorKind::PenceOutOfRange;
+            return Interpretation::Invalid(ParsePriceError { position: pence.0, kind });
+        }
+        if self.fields.len() == 3 && shillings.1.value() >= SHILLINGS_PER_POUND {
+            let kind = ParseErrorKind::ShillingsOutOfRange;
+            return Interpretation::Invalid(ParsePriceError { position: shillings.0, kind });
+        }
+        let price = Price::try_with_farthings(
+            pounds.1.value(),
+            shillings.1.value(),
+            pence_to_farthings(pence.1.value()) + pence.1.fraction(),
+        );
+        let Some(price) = price else {
+            let position = if pounds.1.value() > 0 { pounds.0 } else { shillings.0 };
+            return Interpretation::Invalid(ParsePriceError { position, kind: ParseErrorKind::Overflow });
+        };
+        match pence.1 {
+            Field::Empty => Interpretation::Incomplete(price),
+            _ => Interpretation::Complete(price),
+        }
+    }
+
+    /// Finish parsing, returning the price if the input is complete and valid.
+    pub fn finish(&self) -> Result<Price, ParsePriceError> {
+        match self.current_interpretation() {
+            Interpretation::Complete(price) => Ok(price),
+            Interpretation::Invalid(error) => Err(error),
+            Interpretation::Empty | Interpretation::Incomplete(_) => {
+                Err(ParsePriceError { position: self.len, kind: ParseErrorKind::UnexpectedEnd })
+            }
+        }
+    }
+}
+
+/// Which unit a word in £sd notation gives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Unit {
+    Pounds,
+    Shillings,
+    Pence,
+}
+
+/// Parse a number made of digits and optionally ending in ¼, ½, or ¾, where `start` is the
+/// position of the first character. Returns the number and the fraction in farthings.
+fn parse_number(chars: &[char], start: usize) -> Result<(usize, usize), ParsePriceError> {
+    let mut value: usize = 0;
+    let mut fraction = 0;
+    if chars.is_empty() {
+        return Err(ParsePriceError { position: start, kind: ParseErrorKind::UnexpectedEnd });
+    }
+    for (i, &c) in chars.iter().enumerate() {
+        let position = start + i;
+        match c {
+            '0'..='9' if fraction == 0 => {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(c as usize - '0' as usize))
+                    .ok_or(ParsePriceError { position, kind: ParseErrorKind::Overflow })?;
+            }
+            _ => match fraction_farthings(c) {
+                Some(farthings) if fraction == 0 => fraction = farthings,
+                _ => return Err(ParsePriceError { position, kind: ParseErrorKind::UnexpectedChar(c) }),
+            },
+        }
+    }
+    Ok((value, fraction))
+}
+
+/// Parse £sd notation like `"£3 16s 11d"`, `"10s"`, or `"2¾d"`. Each unit is optional, but they
+/// must come in order.
+pub(crate) fn parse_lsd(s: &str) -> Result<Price, ParsePriceError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut amounts = [None; 3];
+    let mut last_unit = None;
+    let mut position = 0;
+    while position < chars.len() {
+        if chars[position].is_whitespace() {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        while position < chars.len() && !chars[position].is_whitespace() {
+            position += 1;
+        }
+        let word = &chars[start..position];
+        let (unit, digits, digits_start, unit_position) = match word {
+            ['£', rest @ ..] => (Unit::Pounds, rest, start + 1, start),
+            [rest @ .., 's'] => (Unit::Shillings, rest, start, position - 1),
+            [rest @ .., 'd'] => (Unit::Pence, rest, start, position - 1),
+            [.., c] => {
+                let kind = ParseErrorKind::UnexpectedChar(*c);
+                return Err(ParsePriceError { position: position - 1, kind });
+            }
+            [] => unreachable!("words are never empty"),
+        };
+        if last_unit.is_some_and(|last| last >= unit) {
+            let kind = ParseErrorKind::UnexpectedChar(chars[unit_position]);
+            return Err(ParsePriceError { position: unit_position, kind });
+        }
+        last_unit = Some(unit);
+        let (value, fraction) = parse_number(digits, digits_start)?;
+        if fraction > 0 && unit != Unit::Pence {
+            let offset = digits.len() - 1;
+            let kind = ParseErrorKind::UnexpectedChar(digits[offset]);
+            return Err(ParsePriceError { position: digits_start + offset, kind });
+        }
+        amounts[unit as usize] = Some((digits_start, value, fraction));
+    }
+    let [pounds, shillings, pence] = amounts;
+    if pounds.is_none() && shillings.is_none() && pence.is_none() {
+        return Err(ParsePriceError { position: chars.len(), kind: ParseErrorKind::UnexpectedEnd });
+    }
+    if let Some((position, value, _)) = pence {
+        if value >= PENCE_PER_SHILLING {
+            return Err(ParsePriceError { position, kind: ParseErrorKind::PenceOutOfRange });
+        }
+    }
+    if let (Some(_), Some((position, value, _))) = (pounds, shillings) {
+        if value >= SHILLINGS_PER_POUND {
+            return Err(ParsePriceError { position, kind: ParseErrorKind::ShillingsOutOfRange });
+        }
+    }
+    let value = |amount: Option<(usize, usize, usize)>| amount.map_or(0, |(_, v, _)| v);
+    let fraction = pence.map_or(0, |(_, _, fraction)| fraction);
+    let position = pounds.or(shillings).map_or(0, |(position, _, _)| position);
+    Price::try_with_farthings(value(pounds), value(shillings), pence_to_farthings(value(pence)) + fraction)
+        .ok_or(ParsePriceError { position, kind: ParseErrorKind::Overflow })
+}
+
+/// Parses either slash notation (`"3/6"`, `"1/4/-"`, see [`IncrementalParser`]) or £sd notation
+/// (`"£3 16s 11d"`, `"10s"`, `"2d"`). Leading and trailing whitespace is ignored, and error
+/// positions count characters from the start of the original string.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+///
+/// assert_eq!("3/6".parse(), Ok(price!(3/6)));
+/// assert_eq!("1/4/-".parse(), Ok(price!(1/4/-)));
+/// assert_eq!("£3 16s 11d".parse(), Ok(price!(3/16/11)));
+/// assert_eq!("2d".parse(), Ok(price!(-/2)));
+/// assert_eq!("10s".parse(), Ok(price!(10/-)));
+/// assert!("3/6/8/1".parse::<Price>().is_err());
+/// ```
+impl FromStr for Price {
+    type Err = ParsePriceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let offset = s.chars().take_while(|c| c.is_whitespace()).count();
+        let trimmed = s.trim();
+        let result = if trimmed.contains(['£', 's', 'd']) {
+            parse_lsd(trimmed)
+        } else {
+            let mut parser = IncrementalParser::new();
+            parser.push_str(trimmed).and_then(|_| parser.finish())
+        };
+        result.map_err(|e| ParsePriceError { position: e.position + offset, ..e })
+    }
+}
+
+/// Parse a price like [`FromStr`] does, but reject fractions of a penny if the policy says the
+/// halfpenny has been withdrawn.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ HalfpennyPolicy, price };
+/// use coin_changing::parse::{ parse_with, ParseErrorKind };
+///
+/// assert_eq!(parse_with("1/6", HalfpennyPolicy::Withdrawn), Ok(price!(1/6)));
+/// let error = parse_with("1/6½", HalfpennyPolicy::Withdrawn).unwrap_err();
+/// assert_eq!((error.position, error.kind), (3, ParseErrorKind::HalfpennyWithdrawn));
+/// ```
+pub fn parse_with(s: &str, halfpennies: HalfpennyPolicy) -> Result<Price, ParsePriceError> {
+    let price: Price = s.parse()?;
+    if halfpennies == HalfpennyPolicy::Withdrawn && farthings_to_pence(price.to_farthings()).1 > 0 {
+        let position = s.chars().position(|c| fraction_farthings(c).is_some()).unwrap();
+        return Err(ParsePriceError { position, kind: ParseErrorKind::HalfpennyWithdrawn });
+    }
+    Ok(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn interpretation_while_typing() {
+        let mut parser = IncrementalParser::new();
+        let mut seen = Vec::new();
+        for c in "1/4/6½".chars() {
+            parser.push_char(c).unwrap();
+            seen.push(parser.current_interpretation());
+        }
+        assert_eq!(
+            seen,
+            vec![
+                Interpretation::Complete(price!(-/1)),
+                Interpretation::Incomplete(price!(1/-)),
+                Interpretation::Complete(price!(1/4)),
+                Interpretation::Incomplete(price!(1/4/-)),
+                Interpretation::Complete(price!(1/4/6)),
+                Interpretation::Complete(Price::new(1, 4, 13)),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_and_backspace() {
+        let mut parser = IncrementalParser::new();
+        parser.push_str("25/").unwrap();
+        assert_eq!(parser.current_interpretation(), Interpretation::Incomplete(price!(25/-)));
+
+        let mut parser = IncrementalParser::new();
+        parser.push_str("1/25").unwrap();
+        assert_eq!(
+            parser.current_interpretation(),
+            Interpretation::Invalid(ParsePriceError { position: 2, kind: ParseErrorKind::PenceOutOfRange }),
+        );
+        parser.push_char('/').unwrap();
+        assert_eq!(
+            parser.current_interpretation(),
+            Interpretation::Invalid(ParsePriceError { position: 2, kind: ParseErrorKind::ShillingsOutOfRange }),
+        );
+        parser.pop_char();
+        parser.pop_char();
+        let error = ParsePriceError { position: 3, kind: ParseErrorKind::UnexpectedChar('x') };
+        assert_eq!(parser.push_str("x1"), Err(error));
+        assert_eq!(parser.finish(), Err(error));
+        parser.pop_char();
+        parser.pop_char();
+        assert_eq!(parser.finish(), Ok(price!(1/2)));
+    }
+
+    #[test]
+    fn slash_overflow() {
+        let error = "20000000000000000/-/-".parse::<Price>().unwrap_err();
+        assert_eq!((error.position, error.kind), (0, ParseErrorKind::Overflow));
+        let error = " 400000000000000000/6".parse::<Price>().unwrap_err();
+        assert_eq!((error.position, error.kind), (1, ParseErrorKind::Overflow));
+    }
+
+    #[test]
+    fn lsd_overflow() {
+        let error = "£20000000000000000".parse::<Price>().unwrap_err();
+        assert_eq!((error.position, error.kind), (1, ParseErrorKind::Overflow));
+        let error = "400000000000000000s 6d".parse::<Price>().unwrap_err();
+        assert_eq!((error.position, error.kind), (0, ParseErrorKind::Overflow));
+    }
+
+    #[test]
+    fn from_str_lsd() {
+        assert_eq!(" £1 2½d ".parse(), Ok(Price::new(1, 0, 5)));
+        assert_eq!("1s 11¾d".parse(), Ok(Price::with_farthings(0, 1, 47)));
+        assert_eq!("-/¼".parse(), Ok(Price::from_farthings(1)));
+        assert_eq!("25s".parse(), Ok(price!(1/5/-)));
+        assert_eq!(
+            "£1 25s".parse::<Price>(),
+            Err(ParsePriceError { position: 3, kind: ParseErrorKind::ShillingsOutOfRange }),
+        );
+        assert_eq!(
+            "3d 4s".parse::<Price>(),
+            Err(ParsePriceError { position: 4, kind: ParseErrorKind::UnexpectedChar('s') }),
+        );
+        assert_eq!(
+            "  1/x".parse::<Price>(),
+            Err(ParsePriceError { position: 4, kind: ParseErrorKind::UnexpectedChar('x') }),
+        );
+        assert_eq!("".parse::<Price>(), Err(ParsePriceError { position: 0, kind: ParseErrorKind::UnexpectedEnd }));
+    }
+}
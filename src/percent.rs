@@ -0,0 +1,196 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Percentages of prices, for purchase tax, discounts, and markups. The result rarely comes to a
+//! whole farthing, so every calculation takes a [`RoundingPolicy`] saying how to round it.
+
+use core::fmt::Display;
+
+use crate::{
+    rounding::{RoundingDirection, RoundingPolicy, RoundingReport},
+    Price,
+};
+
+/// A percentage, which needn't be a whole number: purchase tax was 33⅓% for years.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::percent::Percent;
+///
+/// assert_eq!(Percent::new(25).to_string(), "25%");
+/// assert_eq!(Percent::from_fraction(100, 3).to_string(), "33 1/3%");
+/// assert_eq!(Percent::from_fraction(50, 2), Percent::new(25));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Percent {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl Percent {
+    pub fn new(percent: u64) -> Self {
+        Percent { numerator: percent, denominator: 1 }
+    }
+
+    /// The percentage `numerator / denominator`, e.g. `Percent::from_fraction(100, 3)` is 33⅓%.
+    ///
+    /// Panics if the denominator is zero.
+    pub fn from_fraction(numerator: u64, denominator: u64) -> Self {
+        assert!(denominator > 0, "a percentage can't have a denominator of zero");
+        let gcd = gcd(numerator, denominator);
+        Percent { numerator: numerator / gcd, denominator: denominator / gcd }
+    }
+
+    pub fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u64 {
+        self.denominator
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+impl Display for Percent {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (whole, rest) = (self.numerator / self.denominator, self.numerator % self.denominator);
+        match (whole, rest) {
+            (_, 0) => write!(f, "{}%", whole),
+            (0, _) => write!(f, "{}/{}%", rest, self.denominator),
+            _ => write!(f, "{} {}/{}%", whole, rest, self.denominator),
+        }
+    }
+}
+
+impl Price {
+    /// This percentage of the price, rounded by `rounding`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Price, price };
+    /// use coin_changing::percent::Percent;
+    /// use coin_changing::rounding::{ RoundingMode, RoundingPolicy };
+    ///
+    /// let purchase_tax = Percent::from_fraction(100, 3);
+    /// assert_eq!(price!(1/1/-).percent(purchase_tax, RoundingPolicy::default()), price!(7/-));
+    /// // A third of 7/11 is 2/7¾ and a bit.
+    /// let tax = price!(7/11).percent(purchase_tax, RoundingPolicy::default());
+    /// assert_eq!(tax.to_string(), "£0 2s 7¾d");
+    /// let to_the_penny = RoundingPolicy::new(RoundingMode::Up, Currency::Penny);
+    /// assert_eq!(price!(7/11).percent(purchase_tax, to_the_penny), price!(2/8));
+    /// ```
+    pub fn percent(&self, percent: Percent, rounding: RoundingPolicy) -> Price {
+        self.percent_with_report(percent, rounding).0
+    }
+
+    /// Like [`Price::percent`], but also reporting what the rounding did.
+    pub fn percent_with_report(&self, percent: Percent, rounding: RoundingPolicy) -> (Price, RoundingReport) {
+        let numerator = self.to_farthings() as u128 * percent.numerator as u128;
+        let (farthings, report) = rounding.round_ratio_with_report(numerator, percent.denominator as u128 * 100);
+        (Price::from_farthings(usize::try_from(farthings).expect("price too large")), report)
+    }
+
+    /// The price with this percentage added on, e.g. tax or a markup. Only the added part is
+    /// rounded, so a price which is already a whole number of coins stays that way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, price };
+    /// use coin_changing::percent::Percent;
+    /// use coin_changing::rounding::{ RoundingMode, RoundingPolicy };
+    ///
+    /// let halfpenny = RoundingPolicy::new(RoundingMode::Nearest, Currency::Halfpenny);
+    /// assert_eq!(price!(10/-).plus_percent(Percent::new(5), halfpenny), price!(10/6));
+    /// ```
+    pub fn plus_percent(&self, percent: Percent, rounding: RoundingPolicy) -> Price {
+        *self + self.percent(percent, rounding)
+    }
+
+    /// Like [`Price::plus_percent`], but also reporting what the rounding did.
+    pub fn plus_percent_with_report(&self, percent: Percent, rounding: RoundingPolicy) -> (Price, RoundingReport) {
+        let (added, report) = self.percent_with_report(percent, rounding);
+        (*self + added, report)
+    }
+
+    /// The price with this percentage taken off, e.g. a discount. Only the part taken off is
+    /// rounded, and the result stops at zero for percentages over 100.
+    pub fn minus_percent(&self, percent: Percent, rounding: RoundingPolicy) -> Price {
+        self.saturating_sub(self.percent(percent, rounding))
+    }
+
+    /// Like [`Price::minus_percent`], but also reporting what the rounding did. Rounding up the
+    /// part taken off rounds the result down, and the other way round.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, price };
+    /// use coin_changing::percent::Percent;
+    /// use coin_changing::rounding::{ RoundingDirection, RoundingMode, RoundingPolicy };
+    ///
+    /// let to_the_penny = RoundingPolicy::new(RoundingMode::Up, Currency::Penny);
+    /// let (price, report) = price!(7/11).minus_percent_with_report(Percent::new(10), to_the_penny);
+    /// assert_eq!(price, price!(7/1));
+    /// assert_eq!(report.direction, RoundingDirection::Down);
+    /// ```
+    pub fn minus_percent_with_report(&self, percent: Percent, rounding: RoundingPolicy) -> (Price, RoundingReport) {
+        let (taken, report) = self.percent_with_report(percent, rounding);
+        let direction = match report.direction {
+            RoundingDirection::Up => RoundingDirection::Down,
+            RoundingDirection::Down => RoundingDirection::Up,
+            RoundingDirection::Exact => RoundingDirection::Exact,
+        };
+        (self.saturating_sub(taken), RoundingReport { direction, ..report })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{price, rounding::RoundingMode, Currency};
+    use alloc::string::ToString;
+
+    #[test]
+    fn rounding_policies() {
+        // 12½% of 1/1 is 1.625d, i.e. 6½ farthings.
+        let eighth = Percent::from_fraction(25, 2);
+        let price = price!(1/1);
+        let with = |mode, to| price.percent(eighth, RoundingPolicy::new(mode, to));
+        assert_eq!(with(RoundingMode::Nearest, Currency::Farthing), Price::from_farthings(7));
+        assert_eq!(with(RoundingMode::Down, Currency::Farthing), Price::from_farthings(6));
+        assert_eq!(with(RoundingMode::Nearest, Currency::Halfpenny), Price::from_farthings(6));
+        assert_eq!(with(RoundingMode::Nearest, Currency::Penny), price!(-/2));
+        assert_eq!(with(RoundingMode::Down, Currency::Penny), price!(-/1));
+        assert_eq!(with(RoundingMode::Up, Currency::Halfpenny), price!(-/2));
+        let (_, report) = price.percent_with_report(eighth, RoundingPolicy::new(RoundingMode::Down, Currency::Penny));
+        assert_eq!((report.direction, report.amount), (RoundingDirection::Down, Price::from_farthings(3)));
+        let (_, report) = price.plus_percent_with_report(Percent::new(25), RoundingPolicy::default());
+        assert!(report.is_exact());
+    }
+
+    #[test]
+    fn discounts_and_markups() {
+        let policy = RoundingPolicy::default();
+        assert_eq!(price!(1/-/-).minus_percent(Percent::new(10), policy), price!(18/-));
+        assert_eq!(price!(1/-/-).minus_percent(Percent::new(150), policy), Price::default());
+        assert_eq!(price!(1/-/-).plus_percent(Percent::from_fraction(100, 3), policy), price!(1/6/8));
+        assert_eq!(Percent::from_fraction(1, 2).to_string(), "1/2%");
+    }
+}
@@ -0,0 +1,35 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The types and functions most code needs, in one import. Things are only ever added here, never
+//! removed or renamed, so a glob import keeps working as the crate grows.
+//!
+//! # Examples
+//!
+//! ```
+//! use coin_changing::prelude::*;
+//!
+//! let change = Wallet::change_for(price!(3/-), ChangePolicy::default());
+//! assert_eq!(change, Wallet { half_crowns: 1, sixpence: 1, ..Default::default() });
+//! assert_eq!(try_coin_change(&[], 1), Err(ChangeError::NoCoins));
+//! ```
+
+pub use crate::balance::Balance;
+pub use crate::era::CurrencySet;
+pub use crate::price;
+pub use crate::{
+    coin_change, try_coin_change, try_coin_change_as, try_coin_change_up_to, try_coin_change_with, Algorithm,
+    ChangeError, ChangeMaker, ChangePolicy, CrownPolicy, Currency, ExtraPolicy, HalfpennyPolicy, PayStrategy,
+    Payment, Price, Wallet, WalletError,
+};
@@ -0,0 +1,379 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bills and receipts: what was bought, how many, at what price, and the total.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::{notation::Notation, percent::Percent, rounding::RoundingPolicy, terminology::Terminology, Price};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineItem {
+    pub description: String,
+    pub quantity: usize,
+    pub unit_price: Price,
+}
+
+impl LineItem {
+    /// The price of the whole line, i.e. the quantity times the unit price. Panics if that's too
+    /// big for a price to hold.
+    pub fn total(&self) -> Price {
+        self.unit_price * self.quantity
+    }
+}
+
+/// Money taken off a bill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Discount {
+    /// A fixed amount off.
+    Amount(Price),
+    /// A percentage of the subtotal off, rounded as the policy says.
+    Percent(Percent, RoundingPolicy),
+}
+
+/// Why a written-out receipt couldn't be read back with [`Receipt::parse`]. Lines count from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptError {
+    /// A line doesn't end in a price in slash notation, or comes after the total.
+    Unreadable { line: usize },
+    /// A line's amount isn't its quantity times its unit price.
+    WrongLine { line: usize, printed: Price, worked_out: Price },
+    /// The subtotal isn't what the lines above it add up to.
+    WrongSubtotal { line: usize, printed: Price, worked_out: Price },
+    /// The total isn't the subtotal less the discounts.
+    WrongTotal { line: usize, printed: Price, worked_out: Price },
+    /// There's no total line.
+    NoTotal,
+}
+
+impl Display for ReceiptError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ReceiptError::Unreadable { line } => write!(f, "line {} isn't part of a receipt", line),
+            ReceiptError::WrongLine { line, printed, worked_out } => {
+                write!(f, "line {} says {} but comes to {}", line, printed, worked_out)
+            }
+            ReceiptError::WrongSubtotal { line, printed, worked_out } => write!(
+                f,
+                "the subtotal on line {} says {} but the lines come to {}",
+                line, printed, worked_out
+            ),
+            ReceiptError::WrongTotal { line, printed, worked_out } => {
+                write!(f, "the total on line {} says {} but the bill comes to {}", line, printed, worked_out)
+            }
+            ReceiptError::NoTotal => write!(f, "the receipt has no total"),
+        }
+    }
+}
+
+impl core::error::Error for ReceiptError {}
+
+/// A bill of line items, with any discounts.
+///
+/// Displaying it writes it out as a shop would have, in slash notation, with the quantity and
+/// unit price for lines of more than one thing. [`Receipt::parse`] reads it back.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::percent::Percent;
+/// use coin_changing::receipt::{ Discount, Receipt };
+/// use coin_changing::rounding::RoundingPolicy;
+///
+/// let mut bill = Receipt::new();
+/// bill.add("Tea", 2, price!(1/6));
+/// bill.add("Candles", 1, Price::with_farthings(0, 0, 18));
+/// bill.discount("Less 10%", Discount::Percent(Percent::new(10), RoundingPolicy::default()));
+/// assert_eq!(bill.subtotal(), Price::with_farthings(0, 3, 18));
+/// assert_eq!(bill.total(), Price::with_farthings(0, 3, 2));
+/// assert_eq!(bill.to_string(), "\
+/// Tea       2 @ 1/6    3/-
+/// Candles             -/4½
+///           Subtotal  3/4½
+/// Less 10%             -/4
+///           Total      3/½
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Receipt {
+    items: Vec<LineItem>,
+    discounts: Vec<(String, Discount)>,
+}
+
+impl Receipt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a line of `quantity` things at `unit_price` each.
+    pub fn add(&mut self, description: impl Into<String>, quantity: usize, unit_price: Price) {
+        self.items.push(LineItem { description: description.into(), quantity, unit_price });
+    }
+
+    /// Take a discount off the bill. Percentage discounts are all worked out on the subtotal, so
+    /// the order they're given in doesn't matter.
+    pub fn discount(&mut self, description: impl Into<String>, discount: Discount) {
+        self.discounts.push((description.into(), discount));
+    }
+
+    pub fn items(&self) -> &[LineItem] {
+        &self.items
+    }
+
+    /// The total of every line, before discounts.
+    pub fn subtotal(&self) -> Price {
+        self.items.iter().map(LineItem::total).sum()
+    }
+
+    /// How much each discount takes off, in the order they were given.
+    pub fn discounts(&self) -> impl Iterator<Item = (&str, Price)> + '_ {
+        let subtotal = self.subtotal();
+        self.discounts.iter().map(move |(description, discount)| {
+            let amount = match *discount {
+                Discount::Amount(amount) => amount,
+                Discount::Percent(percent, rounding) => subtotal.percent(percent, rounding),
+            };
+            (description.as_str(), amount)
+        })
+    }
+
+    /// What's left to pay after the discounts, which is never less than nothing.
+    pub fn total(&self) -> Price {
+        let off: Price = self.discounts().map(|(_, amount)| amount).sum();
+        self.subtotal().saturating_sub(off)
+    }
+
+    /// Read back a receipt written out by [`Display`], checking that every line, the subtotal and
+    /// the total add up. Discounts come back as fixed amounts, since a receipt only shows what
+    /// they took off. [`Receipt::parse_with`] reads receipts in other words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    /// use coin_changing::receipt::{ Receipt, ReceiptError };
+    ///
+    /// let bill = Receipt::parse("\
+    /// Tea       2 @ 1/6    3/-
+    /// Candles             -/4½
+    ///           Subtotal  3/4½
+    /// Less 10%             -/4
+    ///           Total      3/½
+    /// ").unwrap();
+    /// assert_eq!(bill.items()[0].quantity, 2);
+    /// assert_eq!(bill.total(), Price::with_farthings(0, 3, 2));
+    ///
+    /// let overcharged = "Tea  2 @ 1/6  3/-\n     Total  3/6\n";
+    /// assert_eq!(
+    ///     Receipt::parse(overcharged),
+    ///     Err(ReceiptError::WrongTotal { line: 2, printed: price!(3/6), worked_out: price!(3/-) })
+    /// );
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, ReceiptError> {
+        Self::parse_with(text, &Terminology::default())
+    }
+
+    /// Like [`Receipt::parse`], but for a receipt written out by [`Receipt::render`] with `terms`.
+    pub fn parse_with(text: &str, terms: &Terminology) -> Result<Self, ReceiptError> {
+        let slash = |s: &str| Notation::Slash.parse(s).ok();
+        let mut receipt = Receipt::new();
+        let mut subtotal = None;
+        let mut total = None;
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let unreadable = ReceiptError::Unreadable { line: number };
+            if total.is_some() {
+                return Err(unreadable);
+            }
+            // The amount is always the last column, and nothing in it has a space.
+            let (rest, amount) = line.trim_end().rsplit_once(' ').ok_or(unreadable)?;
+            let printed = slash(amount).ok_or(unreadable)?;
+            // The subtotal and total rows leave the description column blank.
+            let label = line.starts_with(' ').then(|| rest.trim());
+            if label == Some(terms.subtotal) && subtotal.is_none() {
+                let worked_out = receipt.subtotal();
+                if printed != worked_out {
+                    return Err(ReceiptError::WrongSubtotal { line: number, printed, worked_out });
+                }
+                subtotal = Some(printed);
+            } else if label == Some(terms.total) {
+                let worked_out = receipt.total();
+                if printed != worked_out {
+                    return Err(ReceiptError::WrongTotal { line: number, printed, worked_out });
+                }
+                total = Some(printed);
+            } else if subtotal.is_some() {
+                receipt.discount(rest.trim(), Discount::Amount(printed));
+            } else {
+                let (description, quantity, unit_price) = match split_quantity(rest, terms.each) {
+                    Some((description, quantity, unit_price)) => (description, quantity, unit_price),
+                    None => (rest.trim(), 1, printed),
+                };
+                let worked_out = unit_price.checked_mul(quantity).ok_or(unreadable)?;
+                if printed != worked_out {
+                    return Err(ReceiptError::WrongLine { line: number, printed, worked_out });
+                }
+                receipt.add(description, quantity, unit_price);
+            }
+        }
+        total.map(|_| receipt).ok_or(ReceiptError::NoTotal)
+    }
+}
+
+/// Split the "2 @ 1/6" off the end of a line's description, if it has one.
+fn split_quantity<'a>(rest: &'a str, each: &str) -> Option<(&'a str, usize, Price)> {
+    let (rest, unit_price) = rest.trim_end().rsplit_once(' ')?;
+    let unit_price = Notation::Slash.parse(unit_price).ok()?;
+    let rest = rest.trim_end().strip_suffix(each)?;
+    let (description, quantity) = rest.trim_end().rsplit_once(' ').unwrap_or(("", rest.trim_end()));
+    let quantity = quantity.parse().ok()?;
+    Some((description.trim(), quantity, unit_price))
+}
+
+impl Receipt {
+    /// Write the receipt out in the words of `terms`. [`Display`] does the same with the default
+    /// [`Terminology`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::price;
+    /// use coin_changing::receipt::Receipt;
+    /// use coin_changing::terminology::Terminology;
+    ///
+    /// let mut bill = Receipt::new();
+    /// bill.add("Te", 2, price!(1/6));
+    /// assert_eq!(bill.render(&Terminology::welsh()), "\
+    /// Te  2 @ 1/6      3/-
+    ///     Is-gyfanswm  3/-
+    ///     Cyfanswm     3/-
+    /// ");
+    /// ```
+    pub fn render(&self, terms: &Terminology) -> String {
+        let mut out = String::new();
+        self.write(&mut out, terms).unwrap();
+        out
+    }
+
+    /// Like [`Receipt::render`], but writing straight into `out`.
+    pub fn write(&self, out: &mut impl Write, terms: &Terminology) -> core::fmt::Result {
+        let slash = |price: Price| Notation::Slash.format(price);
+        let discounts: Vec<(&str, Price)> = self.discounts().collect();
+        // Each row is a description, the quantity column, and an amount.
+        let mut rows: Vec<(&str, String, String)> = Vec::new();
+        for item in &self.items {
+            let quantity = match item.quantity {
+                1 => String::new(),
+                n => format!("{} {} {}", n, terms.each, slash(item.unit_price)),
+            };
+            rows.push((&item.description, quantity, slash(item.total())));
+        }
+        rows.push(("", String::from(terms.subtotal), slash(self.subtotal())));
+        for (description, amount) in discounts {
+            rows.push((description, String::new(), slash(amount)));
+        }
+        rows.push(("", String::from(terms.total), slash(self.total())));
+        let width = |column: fn(&(&str, String, String)) -> usize| rows.iter().map(column).max().unwrap_or(0);
+        let description_width = width(|r| r.0.chars().count());
+        let quantity_width = width(|r| r.1.chars().count());
+        let amount_width = width(|r| r.2.chars().count());
+        for (description, quantity, amount) in &rows {
+            writeln!(
+                out,
+                "{:<dw$}  {:<qw$}  {:>aw$}",
+                description,
+                quantity,
+                amount,
+                dw = description_width,
+                qw = quantity_width,
+                aw = amount_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Receipt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write(f, &Terminology::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+    use alloc::string::ToString;
+
+    #[test]
+    fn totals_and_discounts() {
+        let mut bill = Receipt::new();
+        assert_eq!(bill.total(), Price::default());
+        bill.add("Nails", 144, Price::from_farthings(1));
+        bill.add("Hammer", 1, price!(4/6));
+        assert_eq!(bill.items()[0].total(), price!(3/-));
+        assert_eq!(bill.subtotal(), price!(7/6));
+        bill.discount("Trade", Discount::Amount(price!(1/6)));
+        bill.discount("Cash", Discount::Percent(Percent::new(10), RoundingPolicy::default()));
+        let off: Vec<Price> = bill.discounts().map(|(_, amount)| amount).collect();
+        assert_eq!(off, [price!(1/6), price!(-/9)]);
+        assert_eq!(bill.total(), price!(5/3));
+        bill.discount("Gift", Discount::Amount(price!(1/-/-)));
+        assert_eq!(bill.total(), Price::default());
+    }
+
+    #[test]
+    fn parsing_undoes_rendering() {
+        let mut bill = Receipt::new();
+        bill.add("Nails", 144, Price::from_farthings(1));
+        bill.add("Hammer", 1, price!(4/6));
+        bill.add("Tin tacks", 3, price!(-/2));
+        bill.discount("Trade", Discount::Amount(price!(1/6)));
+        let text = bill.to_string();
+        assert_eq!(Receipt::parse(&text), Ok(bill.clone()));
+        for terms in [Terminology::victorian(), Terminology::welsh()] {
+            assert_eq!(Receipt::parse_with(&bill.render(&terms), &terms), Ok(bill.clone()));
+        }
+        // Percentage discounts come back as what they took off.
+        let mut cash = Receipt::new();
+        cash.add("Hammer", 1, price!(4/6));
+        cash.discount("Cash", Discount::Percent(Percent::new(10), RoundingPolicy::default()));
+        let read = Receipt::parse(&cash.to_string()).unwrap();
+        assert_eq!(read.discounts().collect::<Vec<_>>(), cash.discounts().collect::<Vec<_>>());
+        assert_eq!(read.total(), cash.total());
+    }
+
+    #[test]
+    fn parsing_finds_mistakes() {
+        let mut bill = Receipt::new();
+        bill.add("Tea", 2, price!(1/6));
+        let text = bill.to_string();
+        assert_eq!(
+            Receipt::parse(&text.replacen("3/-", "3/3", 1)),
+            Err(ReceiptError::WrongLine { line: 1, printed: price!(3/3), worked_out: price!(3/-) })
+        );
+        assert_eq!(
+            Receipt::parse("Tea  2 @ 1/6   3/-\n     Subtotal  2/11\n     Total     2/11\n"),
+            Err(ReceiptError::WrongSubtotal { line: 2, printed: price!(2/11), worked_out: price!(3/-) })
+        );
+        assert_eq!(Receipt::parse("Tea  3/-\n"), Err(ReceiptError::NoTotal));
+        assert_eq!(Receipt::parse("Tea  three bob\n"), Err(ReceiptError::Unreadable { line: 1 }));
+        assert_eq!(Receipt::parse(&format!("{}Tea  3/-\n", text)), Err(ReceiptError::Unreadable { line: 4 }));
+    }
+}
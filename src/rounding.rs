@@ -0,0 +1,234 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keeping track of what rounding does to an amount, so an audit can account for every farthing.
+
+use core::fmt::Display;
+
+use crate::{balance::Balance, Currency, Price, CURRENCIES_AS_FARTHINGS};
+
+/// Which way an amount was rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingDirection {
+    Up,
+    Down,
+    /// The amount didn't need rounding.
+    Exact,
+}
+
+/// What rounding did to an amount. Every operation which rounds has a version returning one of
+/// these alongside its result.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ HalfpennyPolicy, Price, price };
+/// use coin_changing::balance::Balance;
+/// use coin_changing::rounding::RoundingDirection;
+///
+/// let (rounded, report) = HalfpennyPolicy::Withdrawn.round_with_report(Price::new(0, 1, 3));
+/// assert_eq!(rounded, price!(1/2));
+/// assert_eq!(report.direction, RoundingDirection::Up);
+/// assert_eq!(report.amount, Price::new(0, 0, 1));
+/// assert_eq!(report.to_string(), "rounded up by £0 0s 0½d (halfpenny withdrawn, round up to the penny)");
+/// assert_eq!(report.adjustment(), Balance::from_farthings(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundingReport {
+    /// A short description of the rule which was applied.
+    pub rule: &'static str,
+    pub direction: RoundingDirection,
+    /// How much was gained (if rounded up) or lost (if rounded down).
+    pub amount: Price,
+}
+
+impl RoundingReport {
+    /// Describe rounding `exact` to `rounded` under `rule`.
+    pub fn new(rule: &'static str, exact: Price, rounded: Price) -> Self {
+        let (direction, amount) = match rounded.cmp(&exact) {
+            core::cmp::Ordering::Greater => (RoundingDirection::Up, rounded - exact),
+            core::cmp::Ordering::Less => (RoundingDirection::Down, exact - rounded),
+            core::cmp::Ordering::Equal => (RoundingDirection::Exact, Price::default()),
+        };
+        RoundingReport { rule, direction, amount }
+    }
+
+    /// Describe rounding `exact / denominator` farthings to `rounded / denominator`, for rounding
+    /// a result which didn't come to a whole farthing. The direction is exact, but the amount is
+    /// to the nearest farthing, since that's the least a [`Price`] can hold.
+    pub fn from_ratio(rule: &'static str, exact: u128, rounded: u128, denominator: u128) -> Self {
+        let (direction, difference) = match rounded.cmp(&exact) {
+            core::cmp::Ordering::Greater => (RoundingDirection::Up, rounded - exact),
+            core::cmp::Ordering::Less => (RoundingDirection::Down, exact - rounded),
+            core::cmp::Ordering::Equal => (RoundingDirection::Exact, 0),
+        };
+        let farthings = (difference * 2 + denominator) / (denominator * 2);
+        let amount = Price::from_farthings(usize::try_from(farthings).expect("rounding by more than a price can hold"));
+        RoundingReport { rule, direction, amount }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.direction == RoundingDirection::Exact
+    }
+
+    /// The change made by rounding: positive if rounded up, negative if rounded down. Summing
+    /// these over a batch of operations gives the net amount gained or lost to rounding.
+    pub fn adjustment(&self) -> Balance {
+        match self.direction {
+            RoundingDirection::Down => Balance::debt(self.amount),
+            RoundingDirection::Up | RoundingDirection::Exact => Balance::from(self.amount),
+        }
+    }
+}
+
+impl Display for RoundingReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.direction {
+            RoundingDirection::Up => write!(f, "rounded up by {} ({})", self.amount, self.rule),
+            RoundingDirection::Down => write!(f, "rounded down by {} ({})", self.amount, self.rule),
+            RoundingDirection::Exact => write!(f, "no rounding needed ({})", self.rule),
+        }
+    }
+}
+
+/// Which way to round an amount which falls between two allowed values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// Whichever is closer, going up when it's exactly halfway.
+    #[default]
+    Nearest,
+    Up,
+    Down,
+}
+
+impl RoundingMode {
+    /// A short description of rounding to a whole number of coins this way, for reports.
+    pub(crate) fn rule(&self) -> &'static str {
+        match self {
+            RoundingMode::Nearest => "round to the nearest coin",
+            RoundingMode::Up => "round up to a whole coin",
+            RoundingMode::Down => "round down to a whole coin",
+        }
+    }
+}
+
+/// How to round an amount which doesn't come out exactly, e.g. tax on a price: in which
+/// direction, and to a multiple of which coin.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::Currency;
+/// use coin_changing::rounding::{ RoundingMode, RoundingPolicy };
+///
+/// // 2 farthings and a third, to the nearest halfpenny.
+/// let policy = RoundingPolicy::new(RoundingMode::Nearest, Currency::Halfpenny);
+/// assert_eq!(policy.round_ratio(7, 3), 2);
+/// assert_eq!(RoundingPolicy::new(RoundingMode::Up, Currency::Penny).round_ratio(7, 3), 4);
+/// assert_eq!(RoundingPolicy::new(RoundingMode::Down, Currency::Penny).round_ratio(7, 3), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoundingPolicy {
+    pub mode: RoundingMode,
+    /// The result is always a whole number of these.
+    pub to: Currency,
+}
+
+impl Default for RoundingPolicy {
+    /// The nearest farthing, i.e. as little rounding as a price allows.
+    fn default() -> Self {
+        RoundingPolicy { mode: RoundingMode::Nearest, to: Currency::Farthing }
+    }
+}
+
+impl RoundingPolicy {
+    pub fn new(mode: RoundingMode, to: Currency) -> Self {
+        RoundingPolicy { mode, to }
+    }
+
+    /// Round `numerator / denominator` farthings to a whole number of [`RoundingPolicy::to`],
+    /// giving the result in farthings.
+    ///
+    /// Panics if the denominator is zero.
+    pub fn round_ratio(&self, numerator: u128, denominator: u128) -> u128 {
+        assert!(denominator > 0, "can't round with a denominator of zero");
+        let step = CURRENCIES_AS_FARTHINGS[self.to as usize] as u128;
+        let whole_denominator = denominator * step;
+        let (steps, remainder) = (numerator / whole_denominator, numerator % whole_denominator);
+        let up = match self.mode {
+            RoundingMode::Nearest => remainder * 2 >= whole_denominator,
+            RoundingMode::Up => remainder > 0,
+            RoundingMode::Down => false,
+        };
+        (steps + up as u128) * step
+    }
+
+    /// Like [`RoundingPolicy::round_ratio`], but also reporting what the rounding did, as
+    /// [`RoundingReport::from_ratio`] describes it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Currency, Price };
+    /// use coin_changing::rounding::{ RoundingDirection, RoundingMode, RoundingPolicy };
+    ///
+    /// let (farthings, report) = RoundingPolicy::new(RoundingMode::Up, Currency::Penny).round_ratio_with_report(7, 3);
+    /// assert_eq!(farthings, 4);
+    /// assert_eq!((report.direction, report.amount), (RoundingDirection::Up, Price::from_farthings(2)));
+    /// ```
+    pub fn round_ratio_with_report(&self, numerator: u128, denominator: u128) -> (u128, RoundingReport) {
+        let rounded = self.round_ratio(numerator, denominator);
+        (rounded, RoundingReport::from_ratio(self.mode.rule(), numerator, rounded * denominator, denominator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn adjustments_net_out() {
+        let reports = [
+            RoundingReport::new("test", price!(1/2), price!(1/3)),
+            RoundingReport::new("test", price!(1/2), price!(1/1)),
+            RoundingReport::new("test", price!(1/2), price!(1/2)),
+        ];
+        assert_eq!(reports.iter().map(|r| r.adjustment()).sum::<Balance>(), Balance::default());
+        assert!(reports[2].is_exact());
+        assert_eq!(reports[1].direction, RoundingDirection::Down);
+    }
+
+    #[test]
+    fn reports_for_ratios() {
+        // A third of a farthing down is still rounding down, even if it's nothing to the farthing.
+        let report = RoundingReport::from_ratio("test", 7, 6, 3);
+        assert_eq!((report.direction, report.amount), (RoundingDirection::Down, Price::default()));
+        let (_, report) = RoundingPolicy::default().round_ratio_with_report(12, 4);
+        assert!(report.is_exact());
+        let (_, report) = RoundingPolicy::new(RoundingMode::Down, Currency::Shilling).round_ratio_with_report(47, 1);
+        assert_eq!(report.amount, Price::from_farthings(47));
+    }
+
+    #[test]
+    fn ratios_round_to_whole_coins() {
+        let nearest = |to| RoundingPolicy::new(RoundingMode::Nearest, to);
+        assert_eq!(RoundingPolicy::default().round_ratio(3, 2), 2);
+        assert_eq!(nearest(Currency::Penny).round_ratio(6, 1), 8);
+        assert_eq!(nearest(Currency::Penny).round_ratio(5, 1), 4);
+        assert_eq!(nearest(Currency::Sixpence).round_ratio(12, 1), 24);
+        assert_eq!(RoundingPolicy::new(RoundingMode::Up, Currency::Shilling).round_ratio(49, 1), 96);
+        assert_eq!(RoundingPolicy::new(RoundingMode::Up, Currency::Shilling).round_ratio(48, 1), 48);
+    }
+}
@@ -0,0 +1,112 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Summarising lots of prices at once, e.g. a digitised price list.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{price, Price};
+
+/// Where the edges between price bands go.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BucketSpec {
+    /// Under 6d, 6d to 1s, 1s to 2/6, 2/6 to 5/-, 5/- to 10/-, 10/- to £1, and £1 or more.
+    #[default]
+    ByShilling,
+    /// Bands split at the given prices. They don't need to be in order.
+    Custom(Vec<Price>),
+}
+
+impl BucketSpec {
+    /// The prices each band starts at, not counting the first band which starts at nothing.
+    pub fn edges(&self) -> Vec<Price> {
+        let mut edges = match self {
+            BucketSpec::ByShilling => {
+                vec![price!(-/6), price!(1/-), price!(2/6), price!(5/-), price!(10/-), price!(1/-/-)]
+            }
+            BucketSpec::Custom(edges) => edges.clone(),
+        };
+        edges.sort();
+        edges.dedup();
+        edges.retain(|&edge| edge > Price::default());
+        edges
+    }
+}
+
+/// How many prices fell in a band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Band {
+    /// The cheapest price in the band.
+    pub from: Price,
+    /// The first price too dear for the band, or [`None`] for the last band.
+    pub to: Option<Price>,
+    pub count: usize,
+}
+
+impl Band {
+    pub fn contains(&self, price: Price) -> bool {
+        self.from <= price && self.to.is_none_or(|to| price < to)
+    }
+}
+
+/// Count how many prices fall in each band, cheapest band first. Every band is returned, even
+/// empty ones.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::stats::{ bucket, BucketSpec };
+///
+/// let prices = [price!(-/4), price!(-/9), price!(1/-), price!(1/11), price!(3/-/-)];
+/// let bands = bucket(prices, BucketSpec::ByShilling);
+/// let counts: Vec<usize> = bands.iter().map(|band| band.count).collect();
+/// assert_eq!(counts, vec![1, 1, 2, 0, 0, 0, 1]);
+/// assert_eq!(bands[2].from, price!(1/-));
+/// assert_eq!(bands[2].to, Some(price!(2/6)));
+///
+/// let bands = bucket(prices, BucketSpec::Custom(vec![price!(1/-/-)]));
+/// assert_eq!((bands[0].count, bands[1].count), (4, 1));
+/// ```
+pub fn bucket(prices: impl IntoIterator<Item = Price>, spec: BucketSpec) -> Vec<Band> {
+    let edges = spec.edges();
+    let mut bands: Vec<Band> = core::iter::once(Price::default())
+        .chain(edges.iter().copied())
+        .zip(edges.iter().copied().map(Some).chain(core::iter::once(None)))
+        .map(|(from, to)| Band { from, to, count: 0 })
+        .collect();
+    for price in prices {
+        bands[edges.partition_point(|&edge| edge <= price)].count += 1;
+    }
+    bands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prices_land_in_one_band() {
+        let prices: Vec<Price> = (0..2000).map(Price::from_farthings).collect();
+        let spec = BucketSpec::Custom(vec![price!(1/-), price!(-/0), price!(-/3), price!(1/-)]);
+        let bands = bucket(prices.iter().copied(), spec);
+        assert_eq!(bands.len(), 3);
+        assert_eq!(bands.iter().map(|band| band.count).sum::<usize>(), prices.len());
+        for price in prices {
+            assert_eq!(bands.iter().filter(|band| band.contains(price)).count(), 1);
+        }
+        assert_eq!(bands[0].count, 12);
+        assert_eq!(bucket([], BucketSpec::Custom(vec![])), vec![Band { from: Price::default(), to: None, count: 0 }]);
+    }
+}
@@ -0,0 +1,130 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splitting a payment between cash, cheques, and postal orders.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{price, Price};
+
+/// A way of paying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instrument {
+    Cash,
+    Cheque,
+    PostalOrder,
+}
+
+/// How much of a payment goes on one instrument. Rules are applied in order, each taking as many
+/// whole `unit`s of what's still owed as it can, up to its `limit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenderRule {
+    pub instrument: Instrument,
+    /// The amount covered is always a multiple of this, e.g. a cheque written for whole pounds.
+    pub unit: Price,
+    /// The most this instrument can cover, if there's a limit.
+    pub limit: Option<Price>,
+}
+
+/// The usual way of splitting a payment: whole pounds by cheque, and the shillings and pence in
+/// cash.
+pub fn standard_rules() -> Vec<TenderRule> {
+    vec![TenderRule { instrument: Instrument::Cheque, unit: price!(1/-/-), limit: None }]
+}
+
+/// How much of a payment goes on each instrument, as returned by [`apportion`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TenderBreakdown {
+    pub cash: Price,
+    pub cheque: Price,
+    pub postal_order: Price,
+}
+
+impl TenderBreakdown {
+    /// The amount going on the given instrument.
+    pub fn get(&self, instrument: Instrument) -> Price {
+        match instrument {
+            Instrument::Cash => self.cash,
+            Instrument::Cheque => self.cheque,
+            Instrument::PostalOrder => self.postal_order,
+        }
+    }
+
+    /// The whole payment.
+    pub fn total(&self) -> Price {
+        self.cash + self.cheque + self.postal_order
+    }
+
+    fn get_mut(&mut self, instrument: Instrument) -> &mut Price {
+        match instrument {
+            Instrument::Cash => &mut self.cash,
+            Instrument::Cheque => &mut self.cheque,
+            Instrument::PostalOrder => &mut self.postal_order,
+        }
+    }
+}
+
+/// Split `amount` between instruments following `rules`. Whatever the rules don't cover is paid
+/// in cash, so the parts always add up to `amount`.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::tender::{ apportion, standard_rules, Instrument, TenderRule };
+///
+/// let breakdown = apportion(price!(3/7/6), &standard_rules());
+/// assert_eq!((breakdown.cheque, breakdown.cash), (price!(3/-/-), price!(7/6)));
+///
+/// // Postal orders for the shillings, up to a pound's worth.
+/// let mut rules = standard_rules();
+/// let limit = Some(price!(1/-/-));
+/// rules.push(TenderRule { instrument: Instrument::PostalOrder, unit: price!(1/-), limit });
+/// let breakdown = apportion(price!(3/7/6), &rules);
+/// assert_eq!(breakdown.postal_order, price!(7/-));
+/// assert_eq!(breakdown.cash, price!(-/6));
+/// ```
+pub fn apportion(amount: Price, rules: &[TenderRule]) -> TenderBreakdown {
+    let mut breakdown = TenderBreakdown::default();
+    let mut remaining = amount.to_farthings();
+    for rule in rules {
+        let unit = rule.unit.to_farthings().max(1);
+        let most = rule.limit.map_or(remaining, |limit| remaining.min(limit.to_farthings()));
+        let covered = most / unit * unit;
+        *breakdown.get_mut(rule.instrument) += Price::from_farthings(covered);
+        remaining -= covered;
+    }
+    breakdown.cash += Price::from_farthings(remaining);
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parts_add_up() {
+        let rules = [
+            TenderRule { instrument: Instrument::PostalOrder, unit: price!(2/6), limit: Some(price!(10/-)) },
+            TenderRule { instrument: Instrument::Cheque, unit: price!(1/-/-), limit: Some(price!(5/-/-)) },
+            TenderRule { instrument: Instrument::PostalOrder, unit: price!(1/-), limit: None },
+        ];
+        let breakdown = apportion(price!(7/3/4), &rules);
+        assert_eq!(breakdown.postal_order, price!(10/-) + price!(1/13/-));
+        assert_eq!(breakdown.cheque, price!(5/-/-));
+        assert_eq!(breakdown.cash, price!(-/4));
+        assert_eq!(breakdown.total(), price!(7/3/4));
+        assert_eq!(apportion(price!(-/9), &standard_rules()).get(Instrument::Cash), price!(-/9));
+    }
+}
@@ -0,0 +1,186 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! The words and date style the statement and receipt renderers use, so books can be written out
+//! the way a particular place or decade would have written them.
+
+use core::fmt::Write;
+
+use crate::ledger::{Date, Side};
+
+/// How dates are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DateStyle {
+    /// The day, the month's name, and the year, like "3 Mar 1952".
+    #[default]
+    DayMonthYear,
+    /// Numbers only, with the year cut to two digits, like "3/3/52".
+    Numeric,
+}
+
+/// The words a statement or receipt is written out with. The default is the crate's usual
+/// English.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::price;
+/// use coin_changing::ledger::{ Date, Ledger };
+/// use coin_changing::terminology::Terminology;
+///
+/// let mut books = Ledger::new();
+/// books.debit(Date::new(1871, 3, 3), "Flour", price!(2/6));
+/// let statement = books.statement(Date::new(1871, 3, 1), Date::new(1871, 3, 31));
+/// assert_eq!(statement.render(&Terminology::victorian()), "\
+/// Account from 1 March 1871 to 31 March 1871
+///               Balance b/f               £0 0s 0d
+/// 3 March 1871  To Flour     Dr £0 2s 6d  (£0 2s 6d)
+///               Balance c/f               (£0 2s 6d)
+/// ");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Terminology {
+    /// Marks money going out, e.g. "Dr".
+    pub debit: &'static str,
+    /// Marks money coming in, e.g. "Cr".
+    pub credit: &'static str,
+    /// Put in front of the description of money going out, like the "To" of "To Flour" in older
+    /// books. Empty for none.
+    pub debit_prefix: &'static str,
+    /// Put in front of the description of money coming in, like "By". Empty for none.
+    pub credit_prefix: &'static str,
+    /// The start of a statement's heading, before the first date.
+    pub statement_from: &'static str,
+    /// Between the dates in a statement's heading.
+    pub statement_to: &'static str,
+    pub brought_forward: &'static str,
+    pub carried_forward: &'static str,
+    pub subtotal: &'static str,
+    pub total: &'static str,
+    /// Between the quantity and the unit price on a receipt, e.g. "@".
+    pub each: &'static str,
+    /// The names of the months, January first.
+    pub months: [&'static str; 12],
+    pub date_style: DateStyle,
+}
+
+impl Default for Terminology {
+    fn default() -> Self {
+        Terminology {
+            debit: "Dr",
+            credit: "Cr",
+            debit_prefix: "",
+            credit_prefix: "",
+            statement_from: "Statement from",
+            statement_to: "to",
+            brought_forward: "Brought forward",
+            carried_forward: "Carried forward",
+            subtotal: "Subtotal",
+            total: "Total",
+            each: "@",
+            months: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+            date_style: DateStyle::DayMonthYear,
+        }
+    }
+}
+
+impl Terminology {
+    /// A Victorian account book: debits "To" and credits "By", balances "b/f" and "c/f", and the
+    /// months written out in full.
+    pub fn victorian() -> Self {
+        Terminology {
+            debit_prefix: "To",
+            credit_prefix: "By",
+            statement_from: "Account from",
+            brought_forward: "Balance b/f",
+            carried_forward: "Balance c/f",
+            months: [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            ..Default::default()
+        }
+    }
+
+    /// Welsh, for the books of a Welsh bank or shop.
+    pub fn welsh() -> Self {
+        Terminology {
+            statement_from: "Datganiad o",
+            statement_to: "i",
+            brought_forward: "Dygwyd ymlaen",
+            carried_forward: "Cariwyd ymlaen",
+            subtotal: "Is-gyfanswm",
+            total: "Cyfanswm",
+            months: ["Ion", "Chwef", "Maw", "Ebr", "Mai", "Meh", "Gorff", "Awst", "Medi", "Hyd", "Tach", "Rhag"],
+            ..Default::default()
+        }
+    }
+
+    /// Write `date` in this terminology's style.
+    pub fn write_date(&self, out: &mut impl Write, date: Date) -> core::fmt::Result {
+        match self.date_style {
+            DateStyle::DayMonthYear => {
+                write!(out, "{} {} {}", date.day(), self.months[date.month() as usize - 1], date.year())
+            }
+            DateStyle::Numeric => write!(out, "{}/{}/{:02}", date.day(), date.month(), date.year() % 100),
+        }
+    }
+
+    /// The mark for money going in or out on `side`.
+    pub fn side(&self, side: Side) -> &'static str {
+        match side {
+            Side::Credit => self.credit,
+            Side::Debit => self.debit,
+        }
+    }
+
+    /// Write a description with the prefix for `side` in front, if there is one.
+    pub fn write_description(&self, out: &mut impl Write, side: Side, description: &str) -> core::fmt::Result {
+        let prefix = match side {
+            Side::Credit => self.credit_prefix,
+            Side::Debit => self.debit_prefix,
+        };
+        match prefix {
+            "" => out.write_str(description),
+            prefix => write!(out, "{} {}", prefix, description),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    #[test]
+    fn date_styles() {
+        let mut out = String::new();
+        let numeric = Terminology { date_style: DateStyle::Numeric, ..Terminology::welsh() };
+        numeric.write_date(&mut out, Date::new(1952, 3, 3)).unwrap();
+        out.push(' ');
+        Terminology::welsh().write_date(&mut out, Date::new(1952, 3, 3)).unwrap();
+        assert_eq!(out, "3/3/52 3 Maw 1952");
+    }
+}
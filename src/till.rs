@@ -0,0 +1,333 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A cash register: a drawer of coins which takes payments and gives change out of what it
+//! actually holds.
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use crate::{
+    tender::{Instrument, TenderBreakdown},
+    Price, Wallet,
+};
+
+/// Why a sale couldn't go through. The till is left as it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TillError {
+    /// The customer didn't hand over enough.
+    Underpaid { short: Price },
+    /// The drawer (along with what the customer handed over) doesn't hold the coins to make the
+    /// change.
+    InsufficientChange { change: Price },
+}
+
+impl Display for TillError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TillError::Underpaid { short } => write!(f, "underpaid by {}", short),
+            TillError::InsufficientChange { change } => {
+                write!(f, "not enough coins in the till to give {} change", change)
+            }
+        }
+    }
+}
+
+impl core::error::Error for TillError {}
+
+/// One sale rung up on a [`Till`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sale {
+    pub price: Price,
+    /// How the price was paid: all in cash, unless the sale was rung up with
+    /// [`Till::sale_with_tender`].
+    pub tender: TenderBreakdown,
+    /// The coins and notes handed over for the cash part.
+    pub tendered: Wallet,
+    pub change: Wallet,
+}
+
+/// A till, starting the day with a float.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, Wallet, price };
+/// use coin_changing::till::{ Till, TillError };
+///
+/// let mut till = Till::new(Wallet { shillings: 2, pennies: 6, ..Default::default() });
+/// let change = till.sale(price!(1/4), Wallet { half_crowns: 1, ..Default::default() }).unwrap();
+/// assert_eq!(change, Wallet { shillings: 1, pennies: 2, ..Default::default() });
+/// // There's no way to give 9d back out of what's left.
+/// let error = till.sale(price!(1/3), Wallet { florins: 1, ..Default::default() });
+/// assert_eq!(error, Err(TillError::InsufficientChange { change: price!(-/9) }));
+/// assert_eq!(till.takings(), price!(1/4));
+/// assert_eq!(till.drawer().to_farthings() - till.float().to_farthings(), price!(1/4).to_farthings());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Till {
+    float: Wallet,
+    drawer: Wallet,
+    sales: Vec<Sale>,
+}
+
+impl Till {
+    pub fn new(float: Wallet) -> Self {
+        Till { float, drawer: float, sales: Vec::new() }
+    }
+
+    /// What the till started with.
+    pub fn float(&self) -> &Wallet {
+        &self.float
+    }
+
+    /// What's in the drawer now.
+    pub fn drawer(&self) -> &Wallet {
+        &self.drawer
+    }
+
+    /// Every sale so far, in order.
+    pub fn sales(&self) -> &[Sale] {
+        &self.sales
+    }
+
+    /// Ring up a sale of `price`, paid with the coins `tendered`. The tendered coins go into the
+    /// drawer and the change comes out of it, using as few coins as possible. Returns the change.
+    pub fn sale(&mut self, price: Price, tendered: Wallet) -> Result<Wallet, TillError> {
+        self.sale_with_tender(TenderBreakdown { cash: price, ..Default::default() }, tendered)
+    }
+
+    /// Ring up a sale paid partly by cheque or postal order, as split up by
+    /// [`apportion`](crate::tender::apportion). The price is the tender's total, and the coins
+    /// `tendered` pay the cash part, with change given as in [`Till::sale`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Wallet, price };
+    /// use coin_changing::tender::{ apportion, standard_rules, Instrument };
+    /// use coin_changing::till::Till;
+    ///
+    /// let mut till = Till::new(Wallet { sixpence: 1, ..Default::default() });
+    /// let tender = apportion(price!(3/9/6), &standard_rules());
+    /// let change = till.sale_with_tender(tender, Wallet { ten_shillings: 1, ..Default::default() });
+    /// assert_eq!(change, Ok(Wallet { sixpence: 1, ..Default::default() }));
+    /// assert_eq!(till.takings(), price!(3/9/6));
+    /// assert_eq!(till.takings_in(Instrument::Cheque), price!(3/-/-));
+    /// ```
+    pub fn sale_with_tender(&mut self, tender: TenderBreakdown, tendered: Wallet) -> Result<Wallet, TillError> {
+        let (price, paid) = (tender.cash, Price::from(tendered));
+        let change_due = paid.checked_sub(price).ok_or_else(|| TillError::Underpaid { short: price - paid })?;
+        let drawer = self.drawer + tendered;
+        let change = drawer
+            .make_change(change_due)
+            .map_err(|_| TillError::InsufficientChange { change: change_due })?;
+        // The change was made out of the drawer, so there's always enough to take it out.
+        self.drawer = drawer.checked_sub(change).unwrap();
+        self.sales.push(Sale { price: tender.total(), tender, tendered, change });
+        Ok(change)
+    }
+
+    /// The total of every sale so far.
+    pub fn takings(&self) -> Price {
+        self.sales.iter().map(|s| s.price).sum()
+    }
+
+    /// How much of the takings came in by `instrument`. Only the cash goes in the drawer.
+    pub fn takings_in(&self, instrument: Instrument) -> Price {
+        self.sales.iter().map(|s| s.tender.get(instrument)).sum()
+    }
+}
+
+/// Why a [`SharedTill`] couldn't ring up a sale.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharedTillError {
+    /// Another thread was using the till. Only returned by the `try_` methods.
+    Busy,
+    /// The till refused the sale.
+    Till(TillError),
+}
+
+#[cfg(feature = "std")]
+impl Display for SharedTillError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SharedTillError::Busy => write!(f, "the till is in use"),
+            SharedTillError::Till(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for SharedTillError {}
+
+#[cfg(feature = "std")]
+impl From<TillError> for SharedTillError {
+    fn from(value: TillError) -> Self {
+        SharedTillError::Till(value)
+    }
+}
+
+/// A [`Till`] which can be shared between threads, e.g. shop assistants in a simulation. Clones
+/// all use the same till. The `try_` methods give [`SharedTillError::Busy`] instead of waiting
+/// when another thread is using it. Only available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Wallet, price };
+/// use coin_changing::till::SharedTill;
+///
+/// let till = SharedTill::new(Wallet::default());
+/// let threads: Vec<_> = (0..4)
+///     .map(|_| {
+///         let till = till.clone();
+///         std::thread::spawn(move || till.sale(price!(-/6), Wallet { sixpence: 1, ..Default::default() }))
+///     })
+///     .collect();
+/// for thread in threads {
+///     assert_eq!(thread.join().unwrap(), Ok(Wallet::default()));
+/// }
+/// assert_eq!(till.takings(), price!(2/-));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct SharedTill {
+    till: std::sync::Arc<std::sync::Mutex<Till>>,
+}
+
+#[cfg(feature = "std")]
+impl SharedTill {
+    pub fn new(float: Wallet) -> Self {
+        SharedTill::from(Till::new(float))
+    }
+
+    /// Wait for the till, then use it. A till left half way through a sale by a panicking thread
+    /// is still usable, since a sale only changes it once it's sure to go through.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Till> {
+        self.till.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn try_lock(&self) -> Result<std::sync::MutexGuard<'_, Till>, SharedTillError> {
+        match self.till.try_lock() {
+            Ok(till) => Ok(till),
+            Err(std::sync::TryLockError::Poisoned(poisoned)) => Ok(poisoned.into_inner()),
+            Err(std::sync::TryLockError::WouldBlock) => Err(SharedTillError::Busy),
+        }
+    }
+
+    /// Like [`Till::sale`], waiting for any other thread to finish with the till first.
+    pub fn sale(&self, price: Price, tendered: Wallet) -> Result<Wallet, TillError> {
+        self.lock().sale(price, tendered)
+    }
+
+    /// Like [`Till::sale`], but giving up straight away if another thread is using the till.
+    pub fn try_sale(&self, price: Price, tendered: Wallet) -> Result<Wallet, SharedTillError> {
+        Ok(self.try_lock()?.sale(price, tendered)?)
+    }
+
+    /// Like [`Till::sale_with_tender`], waiting for any other thread to finish with the till first.
+    pub fn sale_with_tender(&self, tender: TenderBreakdown, tendered: Wallet) -> Result<Wallet, TillError> {
+        self.lock().sale_with_tender(tender, tendered)
+    }
+
+    /// Like [`Till::sale_with_tender`], but giving up straight away if another thread is using the
+    /// till.
+    pub fn try_sale_with_tender(&self, tender: TenderBreakdown, tendered: Wallet) -> Result<Wallet, SharedTillError> {
+        Ok(self.try_lock()?.sale_with_tender(tender, tendered)?)
+    }
+
+    pub fn float(&self) -> Wallet {
+        *self.lock().float()
+    }
+
+    pub fn drawer(&self) -> Wallet {
+        *self.lock().drawer()
+    }
+
+    pub fn sales(&self) -> Vec<Sale> {
+        self.lock().sales().to_vec()
+    }
+
+    pub fn takings(&self) -> Price {
+        self.lock().takings()
+    }
+
+    pub fn takings_in(&self, instrument: Instrument) -> Price {
+        self.lock().takings_in(instrument)
+    }
+
+    /// A copy of the till as it is now.
+    pub fn snapshot(&self) -> Till {
+        self.lock().clone()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Till> for SharedTill {
+    fn from(value: Till) -> Self {
+        SharedTill { till: std::sync::Arc::new(std::sync::Mutex::new(value)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn refused_sales_leave_the_till_alone() {
+        let mut till = Till::new(Wallet::default());
+        let short = till.sale(price!(1/-), Wallet { sixpence: 1, ..Default::default() });
+        assert_eq!(short, Err(TillError::Underpaid { short: price!(-/6) }));
+        let no_change = till.sale(price!(-/6), Wallet { shillings: 1, ..Default::default() });
+        assert_eq!(no_change, Err(TillError::InsufficientChange { change: price!(-/6) }));
+        assert_eq!(till.drawer(), &Wallet::default());
+        let exact = till.sale(price!(1/-), Wallet { sixpence: 2, ..Default::default() });
+        assert_eq!(exact, Ok(Wallet::default()));
+        // Now the sixpences are in the drawer, a shilling can be changed.
+        let change = till.sale(price!(-/6), Wallet { shillings: 1, ..Default::default() });
+        assert_eq!(change, Ok(Wallet { sixpence: 1, ..Default::default() }));
+        assert_eq!(till.sales().len(), 2);
+        assert_eq!(till.takings(), price!(1/6));
+    }
+
+    #[test]
+    fn cash_part_of_mixed_tender() {
+        let mut till = Till::new(Wallet::default());
+        let tender = TenderBreakdown { cash: price!(2/-), cheque: price!(1/-/-), postal_order: price!(5/-) };
+        let short = till.sale_with_tender(tender, Wallet { shillings: 1, ..Default::default() });
+        assert_eq!(short, Err(TillError::Underpaid { short: price!(1/-) }));
+        let exact = till.sale_with_tender(tender, Wallet { florins: 1, ..Default::default() });
+        assert_eq!(exact, Ok(Wallet::default()));
+        assert_eq!(till.sales()[0].price, price!(1/7/-));
+        assert_eq!(till.takings_in(Instrument::Cash), Price::from(*till.drawer()));
+        assert_eq!(till.takings_in(Instrument::PostalOrder), price!(5/-));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn busy_shared_till() {
+        let till = SharedTill::new(Wallet::default());
+        let shilling = Wallet { shillings: 1, ..Default::default() };
+        {
+            let _held = till.lock();
+            assert_eq!(till.clone().try_sale(price!(1/-), shilling), Err(SharedTillError::Busy));
+        }
+        assert_eq!(till.try_sale(price!(1/6), shilling), Err(SharedTillError::Till(TillError::Underpaid { short: price!(-/6) })));
+        assert_eq!(till.try_sale(price!(1/-), shilling), Ok(Wallet::default()));
+        assert_eq!(till.snapshot().drawer(), &shilling);
+    }
+}
@@ -0,0 +1,92 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Working out the best way for a customer and shopkeeper to settle up between them.
+
+use crate::{CoinTable, Price, Wallet, DEFAULT_MAX_TARGET};
+
+/// The coins going each way across the counter for a purchase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Transaction {
+    /// What the buyer hands over.
+    pub tendered: Wallet,
+    /// What the till gives back.
+    pub change: Wallet,
+}
+
+impl Transaction {
+    /// Find the tender and change for `price` which move the fewest coins and notes across the
+    /// counter in total, using only what the buyer and the till actually hold. Among equally good
+    /// ways, the one where the buyer hands over the least is picked. Returns [`None`] if there's
+    /// no way to settle up exactly. Like [`Wallet::pay`], the buyer never hands over as much as the
+    /// price plus their biggest coin, which they could just keep back, and tenders over
+    /// [`DEFAULT_MAX_TARGET`] aren't considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, Wallet, price };
+    /// use coin_changing::transaction::Transaction;
+    ///
+    /// // The till's out of pennies, so add a penny to the florin and get a shilling back.
+    /// let buyer = Wallet { florins: 1, pennies: 1, ..Default::default() };
+    /// let till = Wallet { shillings: 5, sixpence: 5, ..Default::default() };
+    /// let transaction = Transaction::solve(&buyer, &till, price!(1/1)).unwrap();
+    /// assert_eq!(transaction.tendered, Wallet { florins: 1, pennies: 1, ..Default::default() });
+    /// assert_eq!(transaction.change, Wallet { shillings: 1, ..Default::default() });
+    /// ```
+    pub fn solve(buyer: &Wallet, till: &Wallet, price: Price) -> Option<Transaction> {
+        let target = price.to_farthings();
+        let held = buyer.iter().map(|(currency, _)| currency.value_in_farthings());
+        let biggest = held.max().unwrap_or(0);
+        let most = buyer.to_farthings().min(target + biggest.saturating_sub(1)).min(DEFAULT_MAX_TARGET);
+        if most < target {
+            return None;
+        }
+        let tenders = CoinTable::new(buyer, most, false);
+        let changes = CoinTable::new(till, till.to_farthings().min(most - target), false);
+        let (_, tendered) = (target..=most)
+            .filter_map(|t| Some((tenders.count(t)? + changes.count(t - target)?, t)))
+            .min()?;
+        Some(Transaction { tendered: tenders.coins(tendered), change: changes.coins(tendered - target) })
+    }
+
+    /// The total number of coins and notes which change hands.
+    pub fn coins_exchanged(&self) -> usize {
+        self.tendered.to_counts().iter().chain(self.change.to_counts().iter()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn fewest_coins_cross_the_counter() {
+        let buyer = Wallet { florins: 1, pennies: 1, ..Default::default() };
+        let till = Wallet { pennies: 5, shillings: 5, ..Default::default() };
+        let transaction = Transaction::solve(&buyer, &till, price!(1/11)).unwrap();
+        assert_eq!(transaction.tendered, Wallet { florins: 1, ..Default::default() });
+        assert_eq!(transaction.coins_exchanged(), 2);
+        assert_eq!(Transaction::solve(&buyer, &till, price!(2/2)), None);
+        let empty_till = Transaction::solve(&buyer, &Wallet::default(), price!(2/1)).unwrap();
+        assert_eq!(empty_till.change, Wallet::default());
+        let fortune = Wallet { ten_pounds: 2_000, ..Default::default() };
+        assert_eq!(Transaction::solve(&fortune, &till, price!(15_000/-/-)), None);
+        let rich = Wallet { ten_pounds: 500, pennies: 1, ..Default::default() };
+        let penny = Transaction::solve(&rich, &till, price!(-/1)).unwrap();
+        assert_eq!(penny.tendered, Wallet { pennies: 1, ..Default::default() });
+    }
+}
@@ -0,0 +1,211 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checking a proposed transaction, e.g. to mark a student's answer.
+
+use alloc::boxed::Box;
+use core::fmt::Display;
+
+use crate::{ChangePolicy, CrownPolicy, Currency, HalfpennyPolicy, Price, Wallet, CURRENCIES};
+
+/// Why a proposed transaction is wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The money tendered doesn't cover the price.
+    InsufficientTender { price: Price, tendered: Price },
+    /// The change doesn't add up to the tendered amount minus the price.
+    WrongAmount { expected: Price, given: Price },
+    /// The change is the right amount, but uses more coins and notes than it needs to.
+    NotMinimal { given_coins: usize, minimum_coins: usize, suggestion: Box<Wallet> },
+    /// Crowns were tendered, but the [`CrownPolicy`] refuses them.
+    CrownsRefused { crowns: usize },
+    /// Crowns were given as change, but the [`CrownPolicy`] doesn't allow it.
+    CrownsInChange { crowns: usize },
+    /// Halfpennies (or farthings) were tendered or given as change after they were withdrawn.
+    HalfpenniesWithdrawn { halfpennies: usize },
+    /// A coin or note was used which isn't in the policy's [`CurrencySet`](crate::era::CurrencySet),
+    /// e.g. one that wasn't in circulation yet.
+    NotInCirculation { currency: Currency },
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VerificationError::InsufficientTender { price, tendered } => {
+                write!(f, "{} was tendered for a price of {}", tendered, price)
+            }
+            VerificationError::WrongAmount { expected, given } => {
+                write!(f, "change should be {} but {} was given", expected, given)
+            }
+            VerificationError::NotMinimal { given_coins, minimum_coins, .. } => write!(
+                f,
+                "change used {} coins and notes but could have used {}",
+                given_coins, minimum_coins
+            ),
+            VerificationError::CrownsRefused { crowns } => {
+                write!(f, "{} crowns were tendered but aren't accepted", crowns)
+            }
+            VerificationError::CrownsInChange { crowns } => {
+                write!(f, "{} crowns were given as change but shouldn't be", crowns)
+            }
+            VerificationError::HalfpenniesWithdrawn { halfpennies } => {
+                write!(f, "{} halfpennies were used after they were withdrawn", halfpennies)
+            }
+            VerificationError::NotInCirculation { currency } => {
+                write!(f, "{} was used but isn't in circulation", currency)
+            }
+        }
+    }
+}
+
+impl core::error::Error for VerificationError {}
+
+fn coin_count(wallet: &Wallet) -> usize {
+    wallet.to_counts().iter().sum()
+}
+
+/// Check that `change` is correct for paying `price` with `tendered`: the tender must cover the
+/// price, the change must make up the difference exactly, and it must use as few coins and notes
+/// as possible.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, Wallet, price };
+/// use coin_changing::verify::{ verify_change, VerificationError };
+///
+/// let tendered = Wallet { florins: 1, ..Default::default() };
+/// let change = Wallet { pennies: 1, ..Default::default() };
+/// assert_eq!(verify_change(price!(1/11), &tendered, &change), Ok(()));
+///
+/// let change = Wallet { halfpence: 2, ..Default::default() };
+/// assert!(matches!(
+///     verify_change(price!(1/11), &tendered, &change),
+///     Err(VerificationError::NotMinimal { given_coins: 2, minimum_coins: 1, .. }),
+/// ));
+/// ```
+pub fn verify_change(price: Price, tendered: &Wallet, change: &Wallet) -> Result<(), VerificationError> {
+    verify_change_with(price, tendered, change, ChangePolicy::default())
+}
+
+/// Like [`verify_change`], but following a [`ChangePolicy`]: refused crowns mustn't be tendered,
+/// crowns mustn't be given as change unless they're circulating, and once the halfpenny is
+/// withdrawn it can't be used at all and the price is rounded with [`HalfpennyPolicy::round`].
+/// Nothing outside the policy's set of currencies can be used either.
+pub fn verify_change_with(
+    price: Price,
+    tendered: &Wallet,
+    change: &Wallet,
+    policy: impl Into<ChangePolicy>,
+) -> Result<(), VerificationError> {
+    let policy = policy.into();
+    let used = tendered.to_counts().into_iter().zip(change.to_counts()).map(|(t, c)| t + c);
+    let mut used = CURRENCIES.iter().zip(used);
+    if let Some((&currency, _)) = used.find(|&(&c, n)| n > 0 && !policy.currencies.contains(c)) {
+        return Err(VerificationError::NotInCirculation { currency });
+    }
+    if tendered.crowns > 0 && !policy.crowns.accepts_crowns() {
+        return Err(VerificationError::CrownsRefused { crowns: tendered.crowns });
+    }
+    if change.crowns > 0 && policy.crowns != CrownPolicy::Circulating {
+        return Err(VerificationError::CrownsInChange { crowns: change.crowns });
+    }
+    let halfpennies = tendered.halfpence + change.halfpence + tendered.farthings + change.farthings;
+    if halfpennies > 0 && policy.halfpennies == HalfpennyPolicy::Withdrawn {
+        return Err(VerificationError::HalfpenniesWithdrawn { halfpennies });
+    }
+    let price = policy.round(price);
+    let tendered_value = tendered.to_farthings();
+    if tendered_value < price.to_farthings() {
+        return Err(VerificationError::InsufficientTender { price, tendered: Price::from(*tendered) });
+    }
+    let expected = tendered_value - price.to_farthings();
+    if change.to_farthings() != expected {
+        return Err(VerificationError::WrongAmount {
+            expected: Price::from_farthings(expected),
+            given: Price::from(*change),
+        });
+    }
+    let suggestion = Wallet::change_for(Price::from_farthings(expected), policy);
+    let (given_coins, minimum_coins) = (coin_count(change), coin_count(&suggestion));
+    if given_coins > minimum_coins {
+        return Err(VerificationError::NotMinimal { given_coins, minimum_coins, suggestion: Box::new(suggestion) });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+    use alloc::string::ToString;
+
+    #[test]
+    fn reports_arithmetic_mistakes() {
+        let tendered = Wallet { half_crowns: 1, ..Default::default() };
+        assert_eq!(
+            verify_change(price!(3/-), &tendered, &Wallet::default()),
+            Err(VerificationError::InsufficientTender { price: price!(3/-), tendered: price!(2/6) }),
+        );
+        let change = Wallet { threepence: 1, ..Default::default() };
+        assert_eq!(
+            verify_change(price!(2/-), &tendered, &change),
+            Err(VerificationError::WrongAmount { expected: price!(-/6), given: price!(-/3) }),
+        );
+    }
+
+    #[test]
+    fn crown_policy() {
+        let tendered = Wallet { crowns: 2, ..Default::default() };
+        let change = Wallet { crowns: 1, ..Default::default() };
+        assert_eq!(verify_change(price!(5/-), &tendered, &change), Ok(()));
+        assert_eq!(
+            verify_change_with(price!(5/-), &tendered, &change, CrownPolicy::Commemorative),
+            Err(VerificationError::CrownsInChange { crowns: 1 }),
+        );
+        let change = Wallet { half_crowns: 2, ..Default::default() };
+        assert_eq!(verify_change_with(price!(5/-), &tendered, &change, CrownPolicy::Commemorative), Ok(()));
+        assert_eq!(
+            verify_change_with(price!(5/-), &tendered, &change, CrownPolicy::Refused),
+            Err(VerificationError::CrownsRefused { crowns: 2 }),
+        );
+    }
+
+    #[test]
+    fn currencies_by_year() {
+        let tendered = Wallet { ten_shillings: 1, ..Default::default() };
+        let change = Wallet { crowns: 1, ..Default::default() };
+        assert_eq!(verify_change_with(price!(5/-), &tendered, &change, ChangePolicy::for_year(1935)), Ok(()));
+        assert_eq!(
+            verify_change_with(price!(5/-), &tendered, &change, ChangePolicy::for_year(1900)),
+            Err(VerificationError::NotInCirculation { currency: Currency::TenShilling }),
+        );
+        let error = VerificationError::NotInCirculation { currency: Currency::TenShilling };
+        assert_eq!(error.to_string(), "ten shilling note was used but isn't in circulation");
+    }
+
+    #[test]
+    fn halfpenny_policy() {
+        let tendered = Wallet { shillings: 1, ..Default::default() };
+        let change = Wallet { threepence: 1, pennies: 1, ..Default::default() };
+        let price = Price::new(0, 0, 15);
+        assert_eq!(verify_change_with(price, &tendered, &change, HalfpennyPolicy::Withdrawn), Ok(()));
+        let change = Wallet { threepence: 1, pennies: 1, halfpence: 1, ..Default::default() };
+        assert_eq!(verify_change(price, &tendered, &change), Ok(()));
+        assert_eq!(
+            verify_change_with(price, &tendered, &change, HalfpennyPolicy::Withdrawn),
+            Err(VerificationError::HalfpenniesWithdrawn { halfpennies: 1 }),
+        );
+    }
+}
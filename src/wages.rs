@@ -0,0 +1,102 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Making up wage packets the way a payroll clerk would on a Friday.
+
+use alloc::vec::Vec;
+
+use crate::{coin_change, ChangePolicy, CrownPolicy, Currency, ExtraPolicy, Price, Wallet};
+
+/// How a wage packet is made up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketPolicy {
+    /// The biggest note to put in a packet. Wages were usually paid in pound notes, since a
+    /// fiver was hard to spend at the shops.
+    pub largest_note: Currency,
+    /// How crowns are treated. By default they're left out, as nobody wanted them in their pay.
+    pub crowns: CrownPolicy,
+    /// Whether the less usual coins and notes may go in, the ten shilling note above all. They're
+    /// left out by default.
+    pub extras: ExtraPolicy,
+}
+
+impl Default for PacketPolicy {
+    fn default() -> Self {
+        PacketPolicy {
+            largest_note: Currency::OnePound,
+            crowns: CrownPolicy::Commemorative,
+            extras: ExtraPolicy::Excluded,
+        }
+    }
+}
+
+/// Make up a single wage packet: as few coins and notes as possible, without going over the
+/// policy's largest note or using crowns or extras it doesn't allow.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, Wallet, price };
+/// use coin_changing::wages::{ wage_packet, PacketPolicy };
+///
+/// let packet = wage_packet(price!(7/12/6), PacketPolicy::default());
+/// assert_eq!(packet, Wallet { one_pounds: 7, half_crowns: 5, ..Default::default() });
+/// ```
+pub fn wage_packet(amount: Price, policy: PacketPolicy) -> Wallet {
+    let largest = Price::from(policy.largest_note).to_farthings();
+    let denominations: Vec<usize> = ChangePolicy { crowns: policy.crowns, extras: policy.extras, ..Default::default() }
+        .change_denominations()
+        .into_iter()
+        .filter(|&c| c <= largest)
+        .collect();
+    Wallet::from_farthing_coins(&coin_change(&denominations, amount.to_farthings()))
+}
+
+/// Make up a wage packet for each amount, and work out the total cash which has to be ordered
+/// from the bank to fill them all.
+pub fn wage_packets(amounts: &[Price], policy: PacketPolicy) -> (Vec<Wallet>, Wallet) {
+    let packets: Vec<Wallet> = amounts.iter().map(|&a| wage_packet(a, policy)).collect();
+    let mut total = [0; 17];
+    for packet in &packets {
+        for (sum, count) in total.iter_mut().zip(packet.to_counts()) {
+            *sum += count;
+        }
+    }
+    (packets, Wallet::from_counts(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn batch_totals_every_packet() {
+        let (packets, order) = wage_packets(&[price!(6/10/-), Price::new(8, 0, 6)], PacketPolicy::default());
+        assert_eq!(packets[0], Wallet { one_pounds: 6, half_crowns: 4, ..Default::default() });
+        assert_eq!(packets[1], Wallet { one_pounds: 8, threepence: 1, ..Default::default() });
+        assert_eq!(order, Wallet { one_pounds: 14, half_crowns: 4, threepence: 1, ..Default::default() });
+        assert_eq!(order.to_farthings(), (price!(6/10/-) + Price::new(8, 0, 6)).to_farthings());
+    }
+
+    #[test]
+    fn ten_shilling_notes() {
+        let policy = PacketPolicy { extras: ExtraPolicy::Included, ..Default::default() };
+        let packet = wage_packet(price!(6/10/-), policy);
+        assert_eq!(packet, Wallet { one_pounds: 6, ten_shillings: 1, ..Default::default() });
+        // Still nothing bigger than a pound note.
+        let packet = wage_packet(price!(12/-/-), policy);
+        assert_eq!(packet, Wallet { one_pounds: 12, ..Default::default() });
+    }
+}
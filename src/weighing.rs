@@ -0,0 +1,226 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Finding a clipped or sweated coin with a pan balance. Among a pile of coins of one kind, one
+//! may have had metal taken off it; weighing thirds of the pile against each other finds it in as
+//! few weighings as can be done.
+
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use crate::{metal::CoinSpec, Currency, Wallet};
+
+/// Which way the balance went. The lighter pan rises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tilt {
+    Level,
+    LeftLight,
+    RightLight,
+}
+
+/// How to find the one light coin among a pile of otherwise identical coins, numbered from 0.
+///
+/// Each weighing puts a third of the coins still under suspicion in each pan. If one pan rises
+/// the light coin's in it, and if they balance it's in the third left off, so `n` coins take
+/// ⌈log₃ n⌉ weighings, which is the fewest any strategy can promise.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::weighing::{ Strategy, Tilt };
+///
+/// let mut strategy = Strategy::new(9);
+/// assert_eq!(strategy.weighings(), 2);
+/// assert_eq!(strategy.next_weighing(), Some((0..3, 3..6)));
+/// strategy.narrow(Tilt::RightLight);
+/// assert_eq!(strategy.next_weighing(), Some((3..4, 4..5)));
+/// strategy.narrow(Tilt::Level);
+/// assert_eq!(strategy.next_weighing(), None);
+/// assert_eq!(strategy.found(), Some(5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Strategy {
+    suspects: Range<usize>,
+}
+
+impl Strategy {
+    pub fn new(coins: usize) -> Self {
+        Strategy { suspects: 0..coins }
+    }
+
+    /// The coins which might still be the light one.
+    pub fn suspects(&self) -> Range<usize> {
+        self.suspects.clone()
+    }
+
+    /// How many weighings are left at most, however the balance goes.
+    pub fn weighings(&self) -> u32 {
+        let mut weighings = 0;
+        let mut settled = 1usize;
+        while settled < self.suspects.len() {
+            weighings += 1;
+            settled = settled.saturating_mul(3);
+        }
+        weighings
+    }
+
+    /// The coins to put in the left and right pans next, or [`None`] once there's no more than
+    /// one suspect left.
+    pub fn next_weighing(&self) -> Option<(Range<usize>, Range<usize>)> {
+        let Range { start, end } = self.suspects;
+        if end - start < 2 {
+            return None;
+        }
+        let third = (end - start).div_ceil(3);
+        Some((start..start + third, start + third..start + 2 * third))
+    }
+
+    /// Rule out the coins the last weighing cleared.
+    pub fn narrow(&mut self, tilt: Tilt) {
+        if let Some((left, right)) = self.next_weighing() {
+            self.suspects = match tilt {
+                Tilt::LeftLight => left,
+                Tilt::RightLight => right,
+                Tilt::Level => right.end..self.suspects.end,
+            };
+        }
+    }
+
+    /// The light coin, if it's been narrowed down to one.
+    pub fn found(&self) -> Option<usize> {
+        (self.suspects.len() == 1).then_some(self.suspects.start)
+    }
+
+    /// Carry out every weighing with `weigh`, and return the coin it points to. That's [`None`] if
+    /// the pans balanced when they couldn't have with a light coin about.
+    pub fn run(mut self, mut weigh: impl FnMut(Range<usize>, Range<usize>) -> Tilt) -> Option<usize> {
+        while let Some((left, right)) = self.next_weighing() {
+            let tilt = weigh(left, right);
+            self.narrow(tilt);
+        }
+        self.found()
+    }
+}
+
+/// A pile of coins of one kind, as struck in a given year, with what each actually weighs.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Currency, Wallet };
+/// use coin_changing::weighing::Pile;
+///
+/// let purse = Wallet { sovereigns: 12, ..Default::default() };
+/// let mut pile = Pile::from_wallet(&purse, Currency::Sovereign, 1890);
+/// assert_eq!(pile.light_coin(), None);
+/// pile.clip(7, 40);
+/// assert_eq!(pile.light_coin(), Some(7));
+/// assert_eq!(pile.weighings(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pile {
+    spec: CoinSpec,
+    milligrams: Vec<u32>,
+}
+
+impl Pile {
+    /// `count` coins of full weight.
+    pub fn new(currency: Currency, count: usize, year: u32) -> Self {
+        let spec = currency.spec(year);
+        Pile { spec, milligrams: vec![spec.milligrams; count] }
+    }
+
+    /// Every `currency` coin in the wallet.
+    pub fn from_wallet(wallet: &Wallet, currency: Currency, year: u32) -> Self {
+        Self::new(currency, wallet[currency], year)
+    }
+
+    pub fn spec(&self) -> CoinSpec {
+        self.spec
+    }
+
+    pub fn len(&self) -> usize {
+        self.milligrams.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.milligrams.is_empty()
+    }
+
+    /// Take `milligrams` off a coin, as a clipper or sweater would have. Panics if there's no
+    /// such coin.
+    pub fn clip(&mut self, coin: usize, milligrams: u32) {
+        self.milligrams[coin] = self.milligrams[coin].saturating_sub(milligrams);
+    }
+
+    /// Put two sets of coins on the balance.
+    pub fn weigh(&self, left: Range<usize>, right: Range<usize>) -> Tilt {
+        let mass = |coins: Range<usize>| self.milligrams[coins].iter().map(|&m| u64::from(m)).sum::<u64>();
+        match mass(left).cmp(&mass(right)) {
+            core::cmp::Ordering::Less => Tilt::LeftLight,
+            core::cmp::Ordering::Greater => Tilt::RightLight,
+            core::cmp::Ordering::Equal => Tilt::Level,
+        }
+    }
+
+    /// The most weighings [`Pile::light_coin`] takes.
+    pub fn weighings(&self) -> u32 {
+        Strategy::new(self.len()).weighings()
+    }
+
+    /// Find the light coin by following the [`Strategy`], or [`None`] if every coin is of full
+    /// weight. The coin the weighings point to is checked against the standard weight at the end.
+    pub fn light_coin(&self) -> Option<usize> {
+        let coin = Strategy::new(self.len()).run(|left, right| self.weigh(left, right))?;
+        (self.milligrams[coin] < self.spec.milligrams).then_some(coin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_coin_in_the_fewest_weighings() {
+        for count in 1..=30 {
+            let fewest = (0..).find(|&w| 3usize.pow(w) >= count).unwrap();
+            assert_eq!(Strategy::new(count).weighings(), fewest);
+            for light in 0..count {
+                let mut pile = Pile::new(Currency::Shilling, count, 1900);
+                pile.clip(light, 1);
+                let mut weighed = 0;
+                let found = Strategy::new(count).run(|left, right| {
+                    weighed += 1;
+                    pile.weigh(left, right)
+                });
+                assert_eq!(found, Some(light));
+                assert!(weighed <= fewest);
+            }
+        }
+        assert_eq!(Strategy::new(0).weighings(), 0);
+        assert_eq!(Strategy::new(usize::MAX).weighings(), 41);
+    }
+
+    #[test]
+    fn full_weight_piles() {
+        assert_eq!(Pile::new(Currency::Penny, 0, 1900).light_coin(), None);
+        assert_eq!(Pile::new(Currency::Penny, 1, 1900).light_coin(), None);
+        assert_eq!(Pile::new(Currency::Penny, 2, 1900).light_coin(), None);
+        // Two coins balancing leaves nobody to blame.
+        assert_eq!(Strategy::new(2).run(|_, _| Tilt::Level), None);
+        let pile = Pile::from_wallet(&Wallet { crowns: 4, ..Default::default() }, Currency::Crown, 1935);
+        assert_eq!(pile.spec().milligrams, 28_280);
+        assert_eq!(pile.len(), 4);
+    }
+}
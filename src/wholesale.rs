@@ -0,0 +1,128 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Converting between prices each and the per-dozen, per-score, and per-gross prices wholesale
+//! lists were quoted in.
+
+use crate::{rounding::RoundingReport, Price};
+
+/// How many items a price is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lot {
+    Each,
+    /// Twelve.
+    Dozen,
+    /// Twenty.
+    Score,
+    /// A dozen dozen, so 144.
+    Gross,
+}
+
+impl Lot {
+    /// The number of items in the lot.
+    pub fn items(&self) -> usize {
+        match self {
+            Lot::Each => 1,
+            Lot::Dozen => 12,
+            Lot::Score => 20,
+            Lot::Gross => 144,
+        }
+    }
+}
+
+/// A price converted to a different lot by [`convert`], rounded down to the farthing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Converted {
+    pub price: Price,
+    /// What rounding down lost, so that the original price times the items in the new lot is
+    /// always exactly `price` times the items in the old lot plus this.
+    pub remainder: Price,
+}
+
+impl Converted {
+    pub fn is_exact(&self) -> bool {
+        self.remainder == Price::default()
+    }
+}
+
+/// Convert a price for one lot into the price for another. Going to a smaller lot, the remainder
+/// is what's left of the original price after paying for its items at the new price.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::{ Price, price };
+/// use coin_changing::wholesale::{ convert, Lot };
+///
+/// // 4/- a dozen is 4d each, exactly.
+/// let each = convert(price!(4/-), Lot::Dozen, Lot::Each);
+/// assert_eq!(each.price, price!(-/4));
+/// assert!(each.is_exact());
+///
+/// // 1/- a dozen is 1d each, but 1/1 a dozen leaves a penny over.
+/// let each = convert(price!(1/1), Lot::Dozen, Lot::Each);
+/// assert_eq!((each.price, each.remainder), (price!(-/1), price!(-/1)));
+///
+/// assert_eq!(convert(price!(-/3), Lot::Each, Lot::Gross).price, price!(1/16/-));
+/// ```
+pub fn convert(price: Price, from: Lot, to: Lot) -> Converted {
+    convert_with_report(price, from, to).0
+}
+
+/// Like [`convert`], but also reporting what the rounding did. The new price is always rounded
+/// down, by the remainder shared out over the old lot.
+///
+/// # Examples
+///
+/// ```
+/// use coin_changing::price;
+/// use coin_changing::rounding::RoundingDirection;
+/// use coin_changing::wholesale::{ convert_with_report, Lot };
+///
+/// let (_, report) = convert_with_report(price!(1/1), Lot::Dozen, Lot::Each);
+/// assert_eq!(report.direction, RoundingDirection::Down);
+/// assert!(convert_with_report(price!(4/-), Lot::Dozen, Lot::Each).1.is_exact());
+/// ```
+pub fn convert_with_report(price: Price, from: Lot, to: Lot) -> (Converted, RoundingReport) {
+    let scaled = price.to_farthings() * to.items();
+    let converted = Converted {
+        price: Price::from_farthings(scaled / from.items()),
+        remainder: Price::from_farthings(scaled % from.items()),
+    };
+    let (exact, items) = (scaled as u128, from.items() as u128);
+    let report = RoundingReport::from_ratio("round down, keeping the remainder", exact, exact - exact % items, items);
+    (converted, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+
+    #[test]
+    fn conversions_account_for_every_farthing() {
+        let lots = [Lot::Each, Lot::Dozen, Lot::Score, Lot::Gross];
+        for price in [price!(-/1), price!(2/7), price!(1/3/5), Price::from_farthings(1)] {
+            for from in lots {
+                for to in lots {
+                    let converted = convert(price, from, to);
+                    assert_eq!(price * to.items(), converted.price * from.items() + converted.remainder);
+                }
+            }
+        }
+        let per_dozen = convert(price!(1/-), Lot::Score, Lot::Dozen);
+        assert_eq!((per_dozen.price, per_dozen.remainder), (Price::from_farthings(28), Price::from_farthings(16)));
+        assert!(convert(price!(1/-/-), Lot::Gross, Lot::Dozen).remainder < Price::from_farthings(12));
+    }
+}
@@ -0,0 +1,589 @@
+//     Copyright 2024 Charlotte Ausel
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prices written out in English words, like "one pound, two shillings and sixpence".
+
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::{
+    farthings_to_pence,
+    parse::{ParseErrorKind, ParsePriceError},
+    Price, FARTHINGS_PER_HALFPENNY, FARTHINGS_PER_SHILLING, PENCE_PER_SHILLING, SHILLINGS_PER_GUINEA,
+    SHILLINGS_PER_POUND,
+};
+
+const SMALL: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve",
+    "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+const SCALES: [(u64, &str); 6] = [
+    (1_000_000_000_000_000_000, "quintillion"),
+    (1_000_000_000_000_000, "quadrillion"),
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// Write a number under a thousand, the British way: "one hundred and five".
+fn write_hundreds(out: &mut impl Write, n: u64) -> core::fmt::Result {
+    let (hundreds, rest) = (n / 100, n % 100);
+    if hundreds > 0 {
+        write!(out, "{} hundred", SMALL[hundreds as usize])?;
+        if rest == 0 {
+            return Ok(());
+        }
+        out.write_str(" and ")?;
+    }
+    match rest {
+        0..=19 => out.write_str(SMALL[rest as usize]),
+        _ if rest % 10 == 0 => out.write_str(TENS[rest as usize / 10]),
+        _ => write!(out, "{}-{}", TENS[rest as usize / 10], SMALL[rest as usize % 10]),
+    }
+}
+
+/// Write a number in words, e.g. "two thousand and twenty-four".
+pub(crate) fn write_number(out: &mut impl Write, n: u64) -> core::fmt::Result {
+    if n < 1_000 {
+        return write_hundreds(out, n);
+    }
+    let mut rest = n;
+    let mut first = true;
+    for (scale, name) in SCALES {
+        if rest >= scale {
+            if !first {
+                out.write_char(' ')?;
+            }
+            write_hundreds(out, rest / scale)?;
+            write!(out, " {}", name)?;
+            rest %= scale;
+            first = false;
+        }
+    }
+    match rest {
+        0 => Ok(()),
+        1..=99 => {
+            out.write_str(" and ")?;
+            write_hundreds(out, rest)
+        }
+        _ => {
+            out.write_char(' ')?;
+            write_hundreds(out, rest)
+        }
+    }
+}
+
+/// Write the pence and any fraction of a penny, e.g. "sixpence halfpenny" or "three farthings".
+fn write_pence(out: &mut impl Write, pence: usize, farthings: usize) -> core::fmt::Result {
+    match pence {
+        0 => return out.write_str(["", "one farthing", "one halfpenny", "three farthings"][farthings]),
+        1 => out.write_str("one penny")?,
+        _ => write!(out, "{}pence", SMALL[pence])?,
+    }
+    out.write_str(["", " farthing", " halfpenny", " three farthings"][farthings])
+}
+
+/// Write a price in words, listing each non-zero unit with an "and" before the last:
+/// "one pound, two shillings and sixpence". Nothing at all is written as "nothing".
+pub(crate) fn write_words(out: &mut impl Write, price: Price) -> core::fmt::Result {
+    let (pence, farthings) = farthings_to_pence(price.farthings);
+    let parts = [price.pounds > 0, price.shillings > 0, pence > 0 || farthings > 0];
+    let count = parts.iter().filter(|&&p| p).count();
+    if count == 0 {
+        return out.write_str("nothing");
+    }
+    let units = (0..parts.len()).filter(|&unit| parts[unit]);
+    for (written, unit) in units.enumerate() {
+        match written {
+            0 => (),
+            _ if written == count - 1 => out.write_str(" and ")?,
+            _ => out.write_str(", ")?,
+        }
+        match unit {
+            0 => {
+                write_number(out, price.pounds as u64)?;
+                out.write_str(if price.pounds == 1 { " pound" } else { " pounds" })?;
+            }
+            1 => {
+                write_number(out, price.shillings as u64)?;
+                out.write_str(if price.shillings == 1 { " shilling" } else { " shillings" })?;
+            }
+            _ => write_pence(out, pence, farthings)?,
+        }
+    }
+    Ok(())
+}
+
+/// A word of the input, along with the (character) position it starts at.
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    word: &'a str,
+    position: usize,
+}
+
+/// Split the input into words, treating commas and hyphens as spaces.
+fn tokenise(s: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut char_start = 0;
+    for (position, (byte, c)) in s.char_indices().enumerate() {
+        let separator = c.is_whitespace() || c == ',' || c == '-';
+        match (start, separator) {
+            (None, false) => {
+                start = Some(byte);
+                char_start = position;
+            }
+            (Some(from), true) => {
+                tokens.push(Token { word: &s[from..byte], position: char_start });
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(from) = start {
+        tokens.push(Token { word: &s[from..], position: char_start });
+    }
+    tokens
+}
+
+/// The value of a word for a number under a hundred, if it is one.
+fn small_number(word: &str) -> Option<u64> {
+    if word.eq_ignore_ascii_case("a") {
+        return Some(1);
+    }
+    let small = SMALL.iter().position(|w| w.eq_ignore_ascii_case(word));
+    let tens = TENS.iter().skip(2).position(|w| w.eq_ignore_ascii_case(word)).map(|t| (t as u64 + 2) * 10);
+    small.map(|n| n as u64).or(tens)
+}
+
+/// The pence in a single word like "sixpence", from twopence up to elevenpence, including the
+/// spoken "tuppence" and "thruppence".
+fn pence_word(word: &str) -> Option<usize> {
+    let lower = word.to_ascii_lowercase();
+    match lower.as_str() {
+        "tuppence" => return Some(2),
+        "thruppence" => return Some(3),
+        _ => (),
+    }
+    let number = lower.strip_suffix("pence")?;
+    SMALL.iter().position(|w| *w == number).filter(|n| (2..PENCE_PER_SHILLING).contains(n))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Unit {
+    Pounds,
+    Shillings,
+    Pence,
+    /// Farthings and halfpennies after the pence, or on their own.
+    Fraction,
+}
+
+impl Unit {
+    /// The unit a bare number means after this one, as in "two pounds ten" or "five shillings and
+    /// six".
+    fn next(&self) -> Option<Unit> {
+        match self {
+            Unit::Pounds => Some(Unit::Shillings),
+            Unit::Shillings => Some(Unit::Pence),
+            Unit::Pence | Unit::Fraction => None,
+        }
+    }
+}
+
+/// What a unit word means: which unit it is and how many farthings of the fraction each one is
+/// worth (for the other units, one).
+fn unit_word(word: &str) -> Option<(Unit, usize)> {
+    let lower = word.to_ascii_lowercase();
+    match lower.as_str() {
+        "pound" | "pounds" | "quid" => Some((Unit::Pounds, 1)),
+        "shilling" | "shillings" | "bob" => Some((Unit::Shillings, 1)),
+        "penny" | "pence" | "pennies" => Some((Unit::Pence, 1)),
+        "farthing" | "farthings" => Some((Unit::Fraction, 1)),
+        "halfpenny" | "halfpence" | "ha'penny" | "ha'pence" => Some((Unit::Fraction, FARTHINGS_PER_HALFPENNY)),
+        _ => None,
+    }
+}
+
+/// What a coin (or the like) named in a price is worth in farthings, e.g. "crown" or "guineas".
+fn coin_word(word: &str) -> Option<usize> {
+    let shillings = |s| s * FARTHINGS_PER_SHILLING;
+    let lower = word.to_ascii_lowercase();
+    match lower.trim_end_matches('s') {
+        "crown" | "dollar" => Some(shillings(5)),
+        "florin" => Some(shillings(2)),
+        "guinea" => Some(shillings(SHILLINGS_PER_GUINEA)),
+        "sovereign" | "sov" => Some(shillings(SHILLINGS_PER_POUND)),
+        "tanner" => Some(crate::pence_to_farthings(6)),
+        "groat" | "joey" => Some(crate::pence_to_farthings(4)),
+        _ => None,
+    }
+}
+
+/// One piece of a price.
+#[derive(Debug, Clone, Copy)]
+enum Term<'a> {
+    /// A number of some unit, e.g. "two shillings", along with where its number started and its
+    /// value (in farthings for [`Unit::Fraction`]).
+    Unit(Unit, usize, usize),
+    /// Some coins, e.g. "half a crown" or "three guineas", worth this many farthings.
+    Coins(usize),
+    /// A number with no unit after it, whose unit depends on what came before.
+    Bare(Token<'a>, usize),
+}
+
+/// Reads the words of a price one at a time.
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    next: usize,
+    /// The position just past the end of the input, for errors about running out of words.
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser { tokens: tokenise(s), next: 0, end: s.chars().count() }
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.next).copied()
+    }
+
+    /// Whether the next word is `word`, ignoring case.
+    fn next_is(&self, word: &str) -> bool {
+        self.peek().is_some_and(|t| t.word.eq_ignore_ascii_case(word))
+    }
+
+    fn unexpected(token: Token) -> ParsePriceError {
+        let c = token.word.chars().next().unwrap();
+        ParsePriceError { position: token.position, kind: ParseErrorKind::UnexpectedChar(c) }
+    }
+
+    fn ended(&self) -> ParsePriceError {
+        ParsePriceError { position: self.end, kind: ParseErrorKind::UnexpectedEnd }
+    }
+
+    /// Read a number written in words, if there is one next, e.g. "three hundred and twelve".
+    fn number(&mut self) -> Result<Option<(usize, usize)>, ParsePriceError> {
+        let Some(first) = self.peek() else { return Ok(None) };
+        if small_number(first.word).is_none() {
+            return Ok(None);
+        }
+        let overflow = |position| ParsePriceError { position, kind: ParseErrorKind::Overflow };
+        let (mut total, mut current): (u64, u64) = (0, 0);
+        // Whether the last word was "hundred" or a scale, after which an "and" can carry on.
+        let mut after_scale = false;
+        while let Some(token) = self.peek() {
+            if let Some(n) = small_number(token.word) {
+                // "twenty-one" is fine, but "one two" or "twenty thirty" isn't one number.
+                if current % 100 != 0 && (current % 10 != 0 || n >= 10 || current % 100 < 20) {
+                    break;
+                }
+                // "a" only counts as a number on its own, so "half a crown" isn't "half one crown".
+                if token.word.eq_ignore_ascii_case("a") && (current > 0 || total > 0) {
+                    break;
+                }
+                current += n;
+            } else if token.word.eq_ignore_ascii_case("hundred") && current > 0 && current < 10 {
+                current *= 100;
+                after_scale = true;
+                self.next += 1;
+                continue;
+            } else if let Some(&(scale, _)) = SCALES.iter().find(|(_, w)| w.eq_ignore_ascii_case(token.word)) {
+                if current == 0 {
+                    break;
+                }
+                total = current
+                    .checked_mul(scale)
+                    .and_then(|n| n.checked_add(total))
+                    .ok_or(overflow(token.position))?;
+                current = 0;
+                after_scale = true;
+                self.next += 1;
+                continue;
+            } else if token.word.eq_ignore_ascii_case("and")
+                && after_scale
+                && self.tokens.get(self.next + 1).is_some_and(|t| small_number(t.word).is_some())
+            {
+                self.next += 1;
+                after_scale = false;
+                continue;
+            } else {
+                break;
+            }
+            after_scale = false;
+            self.next += 1;
+        }
+        let value = total.checked_add(current).and_then(|n| usize::try_from(n).ok());
+        value.map(|n| Some((first.position, n))).ok_or(overflow(first.position))
+    }
+
+    /// Read a coin name, maybe halved: "crown", "half crown", or "half a guinea". Returns what
+    /// one of them is worth in farthings.
+    fn coin(&mut self) -> Result<usize, ParsePriceError> {
+        let half = self.next_is("half");
+        if half {
+            self.next += 1;
+            if self.next_is("a") {
+                self.next += 1;
+            }
+        }
+        let token = self.peek().ok_or(self.ended())?;
+        // A halfpenny is half a penny, however it's written.
+        let value = match token.word.to_ascii_lowercase().as_str() {
+            "penny" | "pennies" if half => Some(crate::FARTHINGS_PER_PENNY),
+            word => coin_word(word),
+        };
+        let value = value.ok_or(Self::unexpected(token))?;
+        self.next += 1;
+        Ok(if half { value / 2 } else { value })
+    }
+
+    /// Read the next term, e.g. "two shillings", "sixpence", "half a crown", or a bare "ten".
+    fn term(&mut self) -> Result<Term<'a>, ParsePriceError> {
+        let token = self.peek().unwrap();
+        if let Some(pence) = pence_word(token.word) {
+            self.next += 1;
+            return Ok(Term::Unit(Unit::Pence, token.position, pence));
+        }
+        if self.next_is("half") {
+            return self.coin().map(Term::Coins);
+        }
+        let number = self.number()?;
+        let Some(unit_token) = self.peek() else {
+            return match number {
+                Some((_, value)) => Ok(Term::Bare(token, value)),
+                None => Err(self.ended()),
+            };
+        };
+        let overflow = |position| ParsePriceError { position, kind: ParseErrorKind::Overflow };
+        match (unit_word(unit_token.word), number) {
+            (Some((unit, per)), Some((position, value))) => {
+                self.next += 1;
+                Ok(Term::Unit(unit, position, value.checked_mul(per).ok_or(overflow(position))?))
+            }
+            // A lone "farthing" or "halfpenny" after the pence means one of them.
+            (Some((Unit::Fraction, per)), None) => {
+                self.next += 1;
+                Ok(Term::Unit(Unit::Fraction, unit_token.position, per))
+            }
+            (None, Some((position, value))) => {
+                if self.next_is("half") || coin_word(unit_token.word).is_some() {
+                    let each = self.coin()?;
+                    Ok(Term::Coins(value.checked_mul(each).ok_or(overflow(position))?))
+                } else {
+                    Ok(Term::Bare(token, value))
+                }
+            }
+            _ => Err(Self::unexpected(unit_token)),
+        }
+    }
+
+    fn price(&mut self) -> Result<Price, ParsePriceError> {
+        if let [only] = self.tokens[..] {
+            if only.word.eq_ignore_ascii_case("nothing") {
+                self.next += 1;
+                return Ok(Price::default());
+            }
+        }
+        if self.tokens.is_empty() {
+            return Err(self.ended());
+        }
+        let mut amounts: [Option<(usize, usize)>; 4] = [None; 4];
+        let mut last_unit: Option<Unit> = None;
+        let mut coins: Option<usize> = None;
+        while let Some(token) = self.peek() {
+            if token.word.eq_ignore_ascii_case("and") && (last_unit.is_some() || coins.is_some()) {
+                self.next += 1;
+                continue;
+            }
+            let (unit, position, value) = match self.term()? {
+                Term::Unit(unit, position, value) => (unit, position, value),
+                Term::Coins(value) => {
+                    let total = coins.unwrap_or(0).checked_add(value);
+                    let overflow = ParsePriceError { position: token.position, kind: ParseErrorKind::Overflow };
+                    coins = Some(total.ok_or(overflow)?);
+                    continue;
+                }
+                // "one and six" is shillings and pence.
+                Term::Bare(_, value) if last_unit.is_none() && coins.is_none() && self.next_is("and") => {
+                    (Unit::Shillings, token.position, value)
+                }
+                Term::Bare(number, value) => match last_unit.and_then(|u| u.next()) {
+                    Some(unit) => (unit, number.position, value),
+                    None if self.peek().is_none() && last_unit.is_none() => return Err(self.ended()),
+                    None => return Err(Self::unexpected(number)),
+                },
+            };
+            if last_unit.is_some_and(|last| last >= unit) {
+                return Err(Self::unexpected(self.tokens[self.next - 1]));
+            }
+            last_unit = Some(unit);
+            amounts[unit as usize] = Some((position, value));
+        }
+        let [pounds, shillings, pence, fraction] = amounts;
+        if let Some((position, value)) = pence {
+            if value >= PENCE_PER_SHILLING {
+                return Err(ParsePriceError { position, kind: ParseErrorKind::PenceOutOfRange });
+            }
+        }
+        // "three halfpence" is fine on its own, but not after some pence.
+        if let (Some(_), Some((position, value))) = (pence, fraction) {
+            if value >= crate::FARTHINGS_PER_PENNY {
+                return Err(ParsePriceError { position, kind: ParseErrorKind::PenceOutOfRange });
+            }
+        }
+        if let (Some(_), Some((position, value))) = (pounds, shillings) {
+            if value >= SHILLINGS_PER_POUND {
+                return Err(ParsePriceError { position, kind: ParseErrorKind::ShillingsOutOfRange });
+            }
+        }
+        let value = |amount: Option<(usize, usize)>| amount.map_or(0, |(_, v)| v);
+        let farthings = crate::pence_to_farthings(value(pence)) + value(fraction);
+        let position = pounds.or(shillings).map_or(0, |(position, _)| position);
+        let overflow = ParsePriceError { position, kind: ParseErrorKind::Overflow };
+        let price = Price::try_with_farthings(value(pounds), value(shillings), farthings).ok_or(overflow)?;
+        let farthings = price.to_farthings().checked_add(coins.unwrap_or(0)).ok_or(overflow)?;
+        Ok(Price::from_farthings(farthings))
+    }
+}
+
+/// Read a price written out in words, either the way [`write_words`] writes them or the way
+/// people said them. See [`Price::from_english`].
+pub(crate) fn parse_words(s: &str) -> Result<Price, ParsePriceError> {
+    Parser::new(s).price()
+}
+
+impl Price {
+    /// Read a price written out in English, as in old letters and account books. As well as
+    /// everything [`Notation::Words`](crate::notation::Notation::Words) writes, this understands
+    /// the everyday ways of saying prices:
+    ///
+    /// - a bare number after the pounds is shillings, and after the shillings is pence: "two
+    ///   pounds ten", "five shillings and six";
+    /// - two bare numbers joined by "and" are shillings and pence: "one and six";
+    /// - coins, which can be counted and halved: "half a crown", "two florins", "a guinea";
+    /// - slang like "quid", "bob", "tanner", "tuppence", and "ha'penny".
+    ///
+    /// Case and leading or trailing whitespace don't matter, and error positions count characters
+    /// from the start of `s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coin_changing::{ Price, price };
+    ///
+    /// assert_eq!(Price::from_english("two pounds ten"), Ok(price!(2/10/-)));
+    /// assert_eq!(Price::from_english("one and six"), Ok(price!(1/6)));
+    /// assert_eq!(Price::from_english("half a crown"), Ok(price!(2/6)));
+    /// assert_eq!(Price::from_english("Three guineas"), Ok(price!(3/3/-)));
+    /// assert_eq!(Price::from_english("tuppence ha'penny"), Ok(Price::with_farthings(0, 0, 10)));
+    /// assert!(Price::from_english("six").is_err());
+    /// ```
+    pub fn from_english(s: &str) -> Result<Price, ParsePriceError> {
+        let offset = s.chars().take_while(|c| c.is_whitespace()).count();
+        parse_words(s.trim()).map_err(|e| ParsePriceError { position: e.position + offset, ..e })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price;
+    use alloc::string::String;
+
+    fn words(price: Price) -> String {
+        let mut out = String::new();
+        write_words(&mut out, price).unwrap();
+        out
+    }
+
+    fn number(n: u64) -> String {
+        let mut out = String::new();
+        write_number(&mut out, n).unwrap();
+        out
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(number(0), "zero");
+        assert_eq!(number(21), "twenty-one");
+        assert_eq!(number(105), "one hundred and five");
+        assert_eq!(number(2_024), "two thousand and twenty-four");
+        assert_eq!(number(1_200_300), "one million two hundred thousand three hundred");
+        for n in [0, 7, 40, 99, 100, 512, 1_001, 250_000, 3_000_021, usize::MAX as u64] {
+            let written = number(n);
+            let mut parser = Parser::new(&written);
+            assert_eq!(parser.number(), Ok(Some((0, n as usize))), "{}", number(n));
+        }
+    }
+
+    #[test]
+    fn prices_round_trip() {
+        assert_eq!(words(price!(1/2/6)), "one pound, two shillings and sixpence");
+        assert_eq!(words(price!(2/10/-)), "two pounds and ten shillings");
+        assert_eq!(words(Price::with_farthings(0, 0, 7)), "one penny three farthings");
+        assert_eq!(words(Price::from_farthings(2)), "one halfpenny");
+        for farthings in (0..2_000).step_by(7) {
+            let price = Price::from_farthings(farthings);
+            assert_eq!(parse_words(&words(price)), Ok(price), "{}", words(price));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let error = parse_words("sixpence and two shillings").unwrap_err();
+        assert_eq!((error.position, error.kind), (17, ParseErrorKind::UnexpectedChar('s')));
+        let error = parse_words("two pounds three crowns and sixpence sixpence").unwrap_err();
+        assert_eq!((error.position, error.kind), (37, ParseErrorKind::UnexpectedChar('s')));
+        let error = parse_words("one pound, twenty shillings").unwrap_err();
+        assert_eq!((error.position, error.kind), (11, ParseErrorKind::ShillingsOutOfRange));
+        let error = parse_words("twelve pence").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::PenceOutOfRange);
+        assert!(parse_words("").is_err());
+        let error = Price::from_english(" sixpence ten").unwrap_err();
+        assert_eq!((error.position, error.kind), (10, ParseErrorKind::UnexpectedChar('t')));
+        let error = Price::from_english("half a").unwrap_err();
+        assert_eq!((error.position, error.kind), (6, ParseErrorKind::UnexpectedEnd));
+        let error = Price::from_english("one quintillion pounds").unwrap_err();
+        assert_eq!((error.position, error.kind), (0, ParseErrorKind::Overflow));
+    }
+
+    #[test]
+    fn colloquial() {
+        let english = |s| Price::from_english(s).unwrap();
+        assert_eq!(english("one pound ten and six"), price!(1/10/6));
+        assert_eq!(english("two and six"), price!(2/6));
+        assert_eq!(english("ten and sixpence"), price!(10/6));
+        assert_eq!(english("five shillings and six"), price!(5/6));
+        assert_eq!(english("five bob"), price!(5/-));
+        assert_eq!(english("a quid"), price!(1/-/-));
+        assert_eq!(english("a tanner"), price!(-/6));
+        assert_eq!(english("thruppence"), price!(-/3));
+        assert_eq!(english("three ha'pence"), Price::from_farthings(6));
+        assert_eq!(english("a half crown"), price!(2/6));
+        assert_eq!(english("two half-crowns"), price!(5/-));
+        assert_eq!(english("half a guinea"), price!(10/6));
+        assert_eq!(english("half a sovereign"), price!(10/-));
+        assert_eq!(english("half a dollar"), price!(2/6));
+        assert_eq!(english("a crown and sixpence"), price!(5/6));
+        assert_eq!(english("a florin and a tanner"), price!(2/6));
+        assert_eq!(english("two guineas"), price!(2/2/-));
+        assert_eq!(english("a hundred guineas"), price!(105/-/-));
+        assert_eq!(english("A Shilling"), price!(1/-));
+    }
+}